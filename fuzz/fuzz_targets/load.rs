@@ -0,0 +1,56 @@
+//! Load arbitrary bytes as every built-in view.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use x::{BitVec, FromView, View, X};
+
+/// Load `data` as `N::View`, and if it is valid, convert the view to `N`,
+/// which reads every part of it.
+fn load<N: FromView>(data: &[u8]) {
+  if let Ok(view) = N::View::load(data) {
+    N::from_view(view);
+  }
+
+  if let Ok(view) = N::View::load_canonical(data) {
+    N::from_view(view);
+  }
+}
+
+fuzz_target!(|data: &[u8]| {
+  load::<()>(data);
+  load::<bool>(data);
+  load::<char>(data);
+
+  load::<u8>(data);
+  load::<u16>(data);
+  load::<u32>(data);
+  load::<u64>(data);
+  load::<u128>(data);
+  load::<usize>(data);
+
+  load::<i8>(data);
+  load::<i16>(data);
+  load::<i32>(data);
+  load::<i64>(data);
+  load::<i128>(data);
+  load::<isize>(data);
+
+  load::<String>(data);
+  load::<BitVec>(data);
+  load::<Vec<u8>>(data);
+  load::<Vec<char>>(data);
+  load::<Vec<String>>(data);
+  load::<Vec<Vec<bool>>>(data);
+
+  load::<Option<u16>>(data);
+  load::<Option<String>>(data);
+  load::<Option<Option<bool>>>(data);
+  load::<Result<u8, String>>(data);
+  load::<Result<Vec<u64>, char>>(data);
+
+  // Arrays have no native conversion, so are only loaded:
+  let _ = <[bool; 3] as X>::View::load(data);
+  let _ = <[String; 2] as X>::View::load(data);
+  let _ = <[Option<char>; 4] as X>::View::load_canonical(data);
+});
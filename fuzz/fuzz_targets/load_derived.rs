@@ -0,0 +1,63 @@
+//! Load arbitrary bytes as nested derived views, and read every field.
+
+#![no_main]
+#![feature(generic_associated_types)]
+#![allow(incomplete_features)]
+
+use libfuzzer_sys::fuzz_target;
+use x::{BitVec, Serializer, View, X};
+
+#[derive(X)]
+struct Record {
+  id:       u64,
+  name:     String,
+  tags:     Vec<String>,
+  parent:   Option<u32>,
+  children: Vec<Child>,
+  flags:    BitVec,
+  unit:     Unit,
+}
+
+#[derive(X)]
+struct Child {
+  value:  char,
+  result: Result<u8, String>,
+  pair:   Pair,
+  deep:   Vec<Option<Vec<i16>>>,
+}
+
+#[derive(X)]
+struct Pair(bool, [u8; 3]);
+
+#[derive(X)]
+struct Unit;
+
+fn read(record: &RecordView) {
+  record.id.to_native::<u64>();
+  record.name.as_str();
+  record.tags.as_slice().iter().for_each(|tag| {
+    tag.as_str();
+  });
+  record.parent.to_native::<Option<u32>>();
+  record.flags.iter().for_each(drop);
+
+  for child in &record.children {
+    child.value.to_native::<char>();
+    child.result.to_native::<Result<u8, String>>();
+    child.pair.0.to_native::<bool>();
+    child.deep.to_native::<Vec<Option<Vec<i16>>>>();
+  }
+}
+
+fuzz_target!(|data: &[u8]| {
+  if let Ok(record) = RecordView::load(data) {
+    read(record);
+  }
+
+  if let Ok(record) = RecordView::load_canonical(data) {
+    read(record);
+  }
+
+  let _ = ChildView::load(data);
+  let _ = PairView::load(data);
+});
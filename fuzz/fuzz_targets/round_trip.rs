@@ -0,0 +1,81 @@
+//! Check that serializing, loading, and converting back to native is the
+//! identity, and that serialization is canonical.
+
+#![no_main]
+#![feature(generic_associated_types)]
+#![allow(incomplete_features)]
+
+use arbitrary::Arbitrary;
+use core::fmt::Debug;
+use libfuzzer_sys::fuzz_target;
+use x::{BitVec, FromView, Serializer, View, X};
+
+#[derive(X, Arbitrary, Debug, PartialEq)]
+struct Record {
+  a: (),
+  b: bool,
+  c: char,
+  d: u8,
+  e: u128,
+  f: usize,
+  g: i8,
+  h: i64,
+  i: isize,
+  j: String,
+  k: Vec<Option<u16>>,
+  l: Result<Vec<String>, char>,
+  m: Option<Option<bool>>,
+  n: Vec<Vec<i32>>,
+  o: Vec<bool>,
+}
+
+impl FromView for Record {
+  fn from_view(view: &RecordView) -> Self {
+    Self {
+      a: view.a.to_native(),
+      b: view.b.to_native(),
+      c: view.c.to_native(),
+      d: view.d.to_native(),
+      e: view.e.to_native(),
+      f: view.f.to_native(),
+      g: view.g.to_native(),
+      h: view.h.to_native(),
+      i: view.i.to_native(),
+      j: view.j.to_native(),
+      k: view.k.to_native(),
+      l: view.l.to_native(),
+      m: view.m.to_native(),
+      n: view.n.to_native(),
+      o: view.o.to_native(),
+    }
+  }
+}
+
+fn round_trip<N: FromView + Debug + PartialEq>(native: &N) {
+  let buffer = native.serialize_to_vec().unwrap();
+
+  assert_eq!(buffer.len(), native.serialized_size().unwrap());
+
+  let view = N::View::load_canonical(&buffer).unwrap();
+
+  assert_eq!(&N::from_view(view), native);
+}
+
+fuzz_target!(|record: Record| {
+  round_trip(&record.a);
+  round_trip(&record.b);
+  round_trip(&record.c);
+  round_trip(&record.d);
+  round_trip(&record.e);
+  round_trip(&record.f);
+  round_trip(&record.g);
+  round_trip(&record.h);
+  round_trip(&record.i);
+  round_trip(&record.j);
+  round_trip(&record.k);
+  round_trip(&record.l);
+  round_trip(&record.m);
+  round_trip(&record.n);
+  round_trip(&record.o.iter().copied().collect::<BitVec>());
+  round_trip(&record);
+});
@@ -1,7 +1,51 @@
+use crate::common::*;
+
 pub trait Allocator {
   type Output;
 
-  fn write(&mut self, bytes: &[u8], offset: usize);
+  /// Error returned by `Done::done` if serialization fails. Allocators which
+  /// cannot otherwise fail use `LengthMismatch`.
+  type Error: Debug;
+
+  /// Stack used to serialize messages with this allocator.
+  type Stack: Stack;
+
+  /// Write `bytes` at `offset`. Once an error is returned, no further methods
+  /// are called, and the error is returned from `Done::done`.
+  fn write(&mut self, bytes: &[u8], offset: usize) -> Result<(), Self::Error>;
+
+  /// Called when all bytes before `offset` have been written, and will not be
+  /// written again. Allocators may use this to flush or release those bytes.
+  fn commit(&mut self, _offset: usize) -> Result<(), Self::Error> {
+    Ok(())
+  }
+
+  fn finish(self, end: usize) -> Result<Self::Output, Self::Error>;
+
+  /// Provide the stack used to serialize a message. Allocators which are used
+  /// in a loop may return a stack reclaimed from a previous message, to avoid
+  /// allocating a new one.
+  fn stack(&mut self) -> Self::Stack {
+    Self::Stack::default()
+  }
+
+  /// Called with the stack provided by `stack` once serialization is complete.
+  fn reclaim(&mut self, _stack: Self::Stack) {}
+
+  /// Called when a message is nested more deeply than `Self::Stack` can hold.
+  /// If this returns `Ok`, serialization continues, but writes to regions which
+  /// did not fit on the stack are skipped, so the message will be incomplete.
+  fn depth_exceeded(&mut self, error: DepthExceeded) -> Result<(), Self::Error> {
+    panic!(
+      "Message nesting depth exceeded stack capacity of {}.",
+      error.depth
+    );
+  }
 
-  fn finish(self, end: usize) -> Self::Output;
+  /// Called when a slice or array serializer is given more or fewer elements
+  /// than it was created for, for example by an `ExactSizeIterator` which
+  /// reports the wrong length. The message would not be valid, so
+  /// serialization always fails, and the returned error is returned from
+  /// `Done::done`.
+  fn length_mismatch(&mut self, error: LengthMismatch) -> Self::Error;
 }
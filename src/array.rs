@@ -21,10 +21,10 @@ impl<E: X, const SIZE: usize> X for [E; SIZE] {
   }
 }
 
-impl<E: View, const SIZE: usize> View for [E; SIZE] {
+unsafe impl<E: View, const SIZE: usize> View for [E; SIZE] {
   type Serializer<A: Allocator, C: Continuation<A>> = ArraySerializer<A, C, E, SIZE>;
 
-  fn check<'value>(suspect: &'value MaybeUninit<Self>, buffer: &[u8]) -> Result<&'value Self> {
+  fn check(suspect: &MaybeUninit<Self>, validator: &mut Validator) -> Result<()> {
     let pointer: *const [E; SIZE] = suspect.as_ptr();
 
     let pointer = pointer as *const [MaybeUninit<E>; SIZE];
@@ -32,11 +32,7 @@ impl<E: View, const SIZE: usize> View for [E; SIZE] {
     // TODO: is this safe?
     let suspects = unsafe { &*pointer };
 
-    for suspect_element in suspects {
-      View::check(suspect_element, buffer)?;
-    }
-
-    Ok(unsafe { suspect.assume_init_ref() })
+    E::check_slice(suspects, validator)
   }
 }
 
@@ -56,15 +52,16 @@ impl<A: Allocator, C: Continuation<A>, E: View, const SIZE: usize> ArraySerializ
   pub fn element_serializer<N: X<View = E>>(
     self,
   ) -> <N::View as View>::Serializer<A, ArraySerializer<A, C, E, SIZE>> {
-    if self.serialized == SIZE {
-      todo!()
+    let mut state = self.state;
+
+    // Elements past the end of the array are discarded, and reported in `done`.
+    if self.serialized >= SIZE {
+      state.discard();
     }
 
     let serialized = self.serialized;
 
-    let state = self
-      .state
-      .transform(|inner| ArraySeed { serialized, inner });
+    let state = state.transform(|inner| ArraySeed { serialized, inner });
 
     <N::View as View>::Serializer::new(state)
   }
@@ -73,9 +70,9 @@ impl<A: Allocator, C: Continuation<A>, E: View, const SIZE: usize> ArraySerializ
     self.element_serializer::<N>().serialize(&element)
   }
 
-  pub fn done(self) -> C {
+  pub fn done(mut self) -> C {
     if self.serialized != SIZE {
-      todo!()
+      self.state.length_mismatch(SIZE, self.serialized);
     }
 
     self.state.continuation()
@@ -87,9 +84,14 @@ impl<A: Allocator, C: Continuation<A>, E: View, const SIZE: usize> Continuation<
 {
   type Seed = ArraySeed<A, C>;
 
-  fn continuation(state: State<A, Self>) -> Self {
+  fn continuation(mut state: State<A, Self>) -> Self {
     let serialized = state.seed().serialized + 1;
 
+    // Stop discarding, if this element was past the end of the array:
+    if serialized > SIZE {
+      state.pop();
+    }
+
     ArraySerializer {
       element: PhantomData,
       state: state.transform(|seed| seed.inner),
@@ -117,14 +119,67 @@ mod tests {
       .element(0u8)
       .element(1u8)
       .done()
-      .done();
+      .done()
+      .unwrap();
 
     assert_eq!(have, &[0, 1]);
   }
 
   #[test]
-  #[ignore]
+  fn slices() {
+    let array: [&[u8]; 2] = [&[7], &[8, 9]];
+    let serialized = array.serialize_to_vec().unwrap();
+
+    #[rustfmt::skip]
+    assert_eq!(&serialized, &[
+      // first offset
+      32, 0, 0, 0, 0, 0, 0, 0,
+      // first length
+      1, 0, 0, 0, 0, 0, 0, 0,
+      // second offset
+      17, 0, 0, 0, 0, 0, 0, 0,
+      // second length
+      2, 0, 0, 0, 0, 0, 0, 0,
+      // first elements
+      7,
+      // second elements
+      8, 9,
+    ]);
+  }
+
+  #[test]
+  fn too_many() {
+    let have = <[u8; 2]>::store_to_vec()
+      .element(0u8)
+      .element(1u8)
+      .element(2u8)
+      .done()
+      .done();
+
+    assert_eq!(
+      have,
+      Err(LengthMismatch {
+        expected: 2,
+        actual:   3,
+      })
+    );
+  }
+
+  #[test]
+  fn too_few() {
+    let have = <[u8; 3]>::store_to_vec().element(1u8).done().done();
+
+    assert_eq!(
+      have,
+      Err(LengthMismatch {
+        expected: 3,
+        actual:   1,
+      })
+    );
+  }
+
+  #[test]
   fn error() {
-    todo!()
+    err::<[bool; 3]>(&[0, 1, 2], Error::Bool { value: 2 }.at(Segment::Index(2)));
   }
 }
@@ -0,0 +1,82 @@
+use crate::common::*;
+
+/// Default capacity of the stacks used by allocators which do not require
+/// `alloc`.
+pub const DEFAULT_DEPTH: usize = 16;
+
+/// A stack with a fixed capacity of `DEPTH` regions, which does not require
+/// `alloc`.
+pub struct ArrayStack<const DEPTH: usize> {
+  regions: [Range<usize>; DEPTH],
+  len:     usize,
+}
+
+impl<const DEPTH: usize> ArrayStack<DEPTH> {
+  const EMPTY: Range<usize> = 0..0;
+
+  pub fn new() -> Self {
+    Self {
+      regions: [Self::EMPTY; DEPTH],
+      len:     0,
+    }
+  }
+}
+
+impl<const DEPTH: usize> Default for ArrayStack<DEPTH> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<const DEPTH: usize> Stack for ArrayStack<DEPTH> {
+  fn push(&mut self, region: Range<usize>) -> Result<(), DepthExceeded> {
+    if self.len == DEPTH {
+      return Err(DepthExceeded { depth: DEPTH });
+    }
+
+    self.regions[self.len] = region;
+    self.len += 1;
+
+    Ok(())
+  }
+
+  fn pop(&mut self) -> Option<Range<usize>> {
+    if self.len == 0 {
+      return None;
+    }
+
+    self.len -= 1;
+
+    Some(mem::replace(&mut self.regions[self.len], Self::EMPTY))
+  }
+
+  fn clear(&mut self) {
+    self.len = 0;
+  }
+
+  fn regions(&self) -> &[Range<usize>] {
+    &self.regions[..self.len]
+  }
+
+  fn regions_mut(&mut self) -> &mut [Range<usize>] {
+    &mut self.regions[..self.len]
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn push_and_pop() {
+    let mut stack = ArrayStack::<2>::new();
+
+    assert_eq!(stack.push(0..1), Ok(()));
+    assert_eq!(stack.push(1..2), Ok(()));
+    assert_eq!(stack.push(2..3), Err(DepthExceeded { depth: 2 }));
+    assert_eq!(stack.regions(), &[0..1, 1..2]);
+    assert_eq!(stack.pop(), Some(1..2));
+    assert_eq!(stack.pop(), Some(0..1));
+    assert_eq!(stack.pop(), None);
+  }
+}
@@ -0,0 +1,300 @@
+use crate::common::*;
+
+/// A view of a packed sequence of bits, stored eight to a byte.
+///
+/// Bit `i` is stored in byte `i / 8`, at bit position `i % 8`, counting from
+/// the least significant bit. Unused bits in the last byte must be zero.
+#[repr(C)]
+pub struct BitSlice {
+  offset: Offset<u8>,
+  length: Usize,
+}
+
+impl BitSlice {
+  /// Number of bits in the slice.
+  pub fn len(&self) -> usize {
+    self.length.to_native()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  pub fn get(&self, index: usize) -> Option<bool> {
+    if index >= self.len() {
+      return None;
+    }
+
+    Some(bit(self.as_bytes(), index))
+  }
+
+  pub fn count_ones(&self) -> usize {
+    // Unused trailing bits are checked to be zero, so they don't need to be
+    // masked off.
+    self
+      .as_bytes()
+      .iter()
+      .map(|byte| byte.count_ones() as usize)
+      .sum()
+  }
+
+  pub fn iter(&self) -> Bits<'_> {
+    Bits::new(self.as_bytes(), self.len())
+  }
+
+  /// The packed bytes of the slice, including unused trailing bits.
+  pub fn as_bytes(&self) -> &[u8] {
+    let pointer = self.offset.as_ptr();
+    unsafe { slice::from_raw_parts(pointer, byte_len(self.len())) }
+  }
+}
+
+impl Debug for BitSlice {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.debug_list().entries(self.iter()).finish()
+  }
+}
+
+impl<'a> IntoIterator for &'a BitSlice {
+  type IntoIter = Bits<'a>;
+  type Item = bool;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+unsafe impl View for BitSlice {
+  type Serializer<A: Allocator, C: Continuation<A>> = BitSliceSerializer<A, C>;
+
+  fn check(suspect: &MaybeUninit<Self>, validator: &mut Validator) -> Result<()> {
+    let length: &MaybeUninit<Usize> =
+      unsafe { &*((suspect.as_ptr() as *const Offset<u8>).add(1) as *const MaybeUninit<Usize>) };
+
+    let length = length.check(validator)?.to_native();
+
+    let offset = suspect.cast::<Offset<u8>>();
+
+    let bytes = Offset::check(offset, validator, byte_len(length))?;
+
+    // Check that unused trailing bits are zero, so that each bit sequence has a
+    // single valid encoding:
+    if let Some(&last) = bytes.last() {
+      let used = length % 8;
+
+      if used != 0 && last >> used != 0 {
+        return Err(Error::BitSlicePadding { value: last });
+      }
+    }
+
+    Ok(())
+  }
+}
+
+/// Iterator over the bits of a packed bit sequence.
+pub struct Bits<'a> {
+  bytes:  &'a [u8],
+  index:  usize,
+  length: usize,
+}
+
+impl<'a> Bits<'a> {
+  pub(crate) fn new(bytes: &'a [u8], length: usize) -> Self {
+    Self {
+      index: 0,
+      bytes,
+      length,
+    }
+  }
+}
+
+impl<'a> Iterator for Bits<'a> {
+  type Item = bool;
+
+  fn next(&mut self) -> Option<bool> {
+    if self.index == self.length {
+      return None;
+    }
+
+    let value = bit(self.bytes, self.index);
+    self.index += 1;
+    Some(value)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let remaining = self.length - self.index;
+    (remaining, Some(remaining))
+  }
+}
+
+impl<'a> ExactSizeIterator for Bits<'a> {}
+
+pub struct BitSliceSerializer<A: Allocator, C: Continuation<A>> {
+  state: State<A, C>,
+}
+
+impl<A: Allocator, C: Continuation<A>> Serializer<A, C> for BitSliceSerializer<A, C> {
+  fn new(state: State<A, C>) -> Self {
+    Self { state }
+  }
+}
+
+impl<A: Allocator, C: Continuation<A>> BitSliceSerializer<A, C> {
+  /// Serialize the bits yielded by `iter`, packing eight to a byte.
+  pub fn serialize_iterator<I: ExactSizeIterator<Item = bool>>(mut self, mut iter: I) -> C {
+    let length = iter.len();
+
+    // Offsets are relative to their own location:
+    let offset = self.state.end() - self.state.position();
+    self.state.write(&offset.to_u64().to_le_bytes());
+    self.state.write(&length.to_u64().to_le_bytes());
+    self.state.push(byte_len(length));
+
+    let mut actual = 0;
+    let mut byte = 0u8;
+    // Extra bits have no space allocated for them, and are not written:
+    for (index, value) in iter.by_ref().take(length).enumerate() {
+      actual += 1;
+
+      if value {
+        byte |= 1 << (index % 8);
+      }
+
+      if index % 8 == 7 {
+        self.state.write(&[byte]);
+        byte = 0;
+      }
+    }
+
+    if actual % 8 != 0 {
+      self.state.write(&[byte]);
+    }
+
+    let actual = actual + iter.count();
+
+    if actual != length {
+      self.state.length_mismatch(length, actual);
+    }
+
+    self.state.pop();
+
+    self.state.continuation()
+  }
+}
+
+/// Number of bytes needed to store `length` bits.
+pub(crate) fn byte_len(length: usize) -> usize {
+  length.div_ceil(8)
+}
+
+fn bit(bytes: &[u8], index: usize) -> bool {
+  bytes[index / 8] & 1 << (index % 8) != 0
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  #[rustfmt::skip]
+  fn basic() {
+    ok(BitVec::new(), &[
+      // offset
+      16, 0, 0, 0, 0, 0, 0, 0,
+      // length
+      0, 0, 0, 0, 0, 0, 0, 0,
+    ]);
+
+    ok([true, false, true].iter().copied().collect::<BitVec>(), &[
+      // offset
+      16, 0, 0, 0, 0, 0, 0, 0,
+      // length
+      3, 0, 0, 0, 0, 0, 0, 0,
+      // bits
+      0b101,
+    ]);
+
+    ok([true; 9].iter().copied().collect::<BitVec>(), &[
+      // offset
+      16, 0, 0, 0, 0, 0, 0, 0,
+      // length
+      9, 0, 0, 0, 0, 0, 0, 0,
+      // bits
+      0xFF, 0b1,
+    ]);
+  }
+
+  #[test]
+  fn lying() {
+    fn lying(bits: &[bool], length: usize) -> Result<Vec<u8>, LengthMismatch> {
+      BitVec::store_to_vec()
+        .serialize_iterator(Lying {
+          inner: bits.iter().copied(),
+          length,
+        })
+        .done()
+    }
+
+    let bits = [true; 10];
+
+    assert_eq!(
+      lying(&bits, 9),
+      Err(LengthMismatch {
+        expected: 9,
+        actual:   10,
+      })
+    );
+
+    assert_eq!(
+      lying(&bits, 17),
+      Err(LengthMismatch {
+        expected: 17,
+        actual:   10,
+      })
+    );
+  }
+
+  #[test]
+  fn access() {
+    let bits = [
+      false, true, true, false, false, false, false, false, true, true,
+    ];
+    let buffer = bits
+      .iter()
+      .copied()
+      .collect::<BitVec>()
+      .serialize_to_vec()
+      .unwrap();
+    let view = BitSlice::load(&buffer).unwrap();
+
+    assert_eq!(view.len(), 10);
+    assert_eq!(view.count_ones(), 4);
+    assert_eq!(view.get(0), Some(false));
+    assert_eq!(view.get(1), Some(true));
+    assert_eq!(view.get(9), Some(true));
+    assert_eq!(view.get(10), None);
+    assert_eq!(view.iter().collect::<Vec<bool>>(), bits);
+  }
+
+  #[test]
+  #[rustfmt::skip]
+  fn error_padding() {
+    err::<BitSlice>(&[
+      // offset
+      16, 0, 0, 0, 0, 0, 0, 0,
+      // length
+      3, 0, 0, 0, 0, 0, 0, 0,
+      // bits
+      0b1101,
+    ], Error::BitSlicePadding { value: 0b1101 });
+  }
+
+  #[test]
+  fn byte_len() {
+    assert_eq!(super::byte_len(0), 0);
+    assert_eq!(super::byte_len(1), 1);
+    assert_eq!(super::byte_len(8), 1);
+    assert_eq!(super::byte_len(9), 2);
+    assert_eq!(super::byte_len(usize::MAX), usize::MAX / 8 + 1);
+  }
+}
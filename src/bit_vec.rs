@@ -0,0 +1,121 @@
+use crate::common::*;
+
+/// A growable sequence of bits, stored eight to a byte.
+///
+/// Serializes as a `BitSlice`, which is eight times smaller than the one byte
+/// per element used by `Vec<bool>`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BitVec {
+  bytes:  Vec<u8>,
+  length: usize,
+}
+
+impl BitVec {
+  pub fn new() -> BitVec {
+    BitVec::default()
+  }
+
+  pub fn len(&self) -> usize {
+    self.length
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.length == 0
+  }
+
+  pub fn push(&mut self, value: bool) {
+    if self.length.is_multiple_of(8) {
+      self.bytes.push(0);
+    }
+
+    if value {
+      let last = self.bytes.len() - 1;
+      self.bytes[last] |= 1 << (self.length % 8);
+    }
+
+    self.length += 1;
+  }
+
+  pub fn get(&self, index: usize) -> Option<bool> {
+    if index >= self.length {
+      return None;
+    }
+
+    Some((self.bytes[index / 8] >> (index % 8)) & 1 == 1)
+  }
+
+  pub fn count_ones(&self) -> usize {
+    self
+      .bytes
+      .iter()
+      .map(|byte| byte.count_ones() as usize)
+      .sum()
+  }
+
+  pub fn iter(&self) -> Bits<'_> {
+    Bits::new(&self.bytes, self.length)
+  }
+}
+
+impl FromIterator<bool> for BitVec {
+  fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+    let mut bits = BitVec::new();
+    for value in iter {
+      bits.push(value);
+    }
+    bits
+  }
+}
+
+impl<'a> IntoIterator for &'a BitVec {
+  type IntoIter = Bits<'a>;
+  type Item = bool;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl X for BitVec {
+  type View = BitSlice;
+
+  fn serialize<A: Allocator, C: Continuation<A>>(
+    &self,
+    serializer: <Self::View as View>::Serializer<A, C>,
+  ) -> C {
+    serializer.serialize_iterator(self.iter())
+  }
+}
+
+impl FromView for BitVec {
+  fn from_view(view: &Self::View) -> Self {
+    BitVec {
+      bytes:  view.as_bytes().into(),
+      length: view.len(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn push() {
+    let mut bits = BitVec::new();
+    assert!(bits.is_empty());
+
+    for i in 0..17 {
+      bits.push(i % 3 == 0);
+    }
+
+    assert_eq!(bits.len(), 17);
+    assert_eq!(bits.count_ones(), 6);
+    assert_eq!(bits.get(0), Some(true));
+    assert_eq!(bits.get(1), Some(false));
+    assert_eq!(bits.get(15), Some(true));
+    assert_eq!(bits.get(16), Some(false));
+    assert_eq!(bits.get(17), None);
+    assert_eq!(bits.bytes, &[0b01001001, 0b10010010, 0b0]);
+  }
+}
@@ -20,10 +20,10 @@ pub struct BoolSerializer<A: Allocator, C: Continuation<A>> {
   state: State<A, C>,
 }
 
-impl View for bool {
+unsafe impl View for bool {
   type Serializer<A: Allocator, C: Continuation<A>> = BoolSerializer<A, C>;
 
-  fn check<'value>(suspect: &'value MaybeUninit<Self>, _buffer: &[u8]) -> Result<&'value Self> {
+  fn check(suspect: &MaybeUninit<Self>, _validator: &mut Validator) -> Result<()> {
     assert_eq!(mem::size_of::<bool>(), 1);
 
     let pointer = suspect.as_ptr() as *const u8;
@@ -37,9 +37,31 @@ impl View for bool {
       return Err(Error::Bool { value });
     }
 
-    // This is safe, because the contained bit pattern is that of either true or
-    // false, which are valid bools.
-    Ok(unsafe { suspect.assume_init_ref() })
+    Ok(())
+  }
+
+  fn check_slice(suspects: &[MaybeUninit<Self>], _validator: &mut Validator) -> Result<()> {
+    // This is safe because the size of a bool is equal to the size of a u8, and
+    // suspects are always taken from an initialized buffer.
+    let bytes = unsafe { slice::from_raw_parts(suspects.as_ptr() as *const u8, suspects.len()) };
+
+    // Skip words in which no byte has any bit set other than the lowest, which
+    // are all valid:
+    let valid = bytes
+      .chunks_exact(8)
+      .take_while(|&word| {
+        u64::from_ne_bytes(word.try_into().unwrap()) & !0x0101_0101_0101_0101 == 0
+      })
+      .count()
+      * 8;
+
+    for (index, &value) in bytes.iter().enumerate().skip(valid) {
+      if value != bool_bit_pattern(true) && value != bool_bit_pattern(false) {
+        return Err(Error::Bool { value }.at(Segment::Index(index)));
+      }
+    }
+
+    Ok(())
   }
 }
 
@@ -69,4 +91,22 @@ mod tests {
     ok(false, &[0x00]);
     ok(true, &[0x01]);
   }
+
+  #[test]
+  fn check_slice() {
+    for length in 0..20 {
+      let mut buffer = vec![true; length].serialize_to_vec().unwrap();
+      <Vec<bool>>::view(&buffer).unwrap();
+
+      for index in 0..length {
+        let value = buffer[16 + index];
+        buffer[16 + index] = 2;
+        assert_eq!(
+          <Vec<bool>>::view(&buffer).err().unwrap(),
+          Error::Bool { value: 2 }.at(Segment::Index(index))
+        );
+        buffer[16 + index] = value;
+      }
+    }
+  }
 }
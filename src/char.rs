@@ -28,10 +28,10 @@ pub struct Char {
   le_bytes: [u8; 3],
 }
 
-impl View for Char {
+unsafe impl View for Char {
   type Serializer<A: Allocator, C: Continuation<A>> = CharSerializer<A, C>;
 
-  fn check<'value>(suspect: &'value MaybeUninit<Self>, _buffer: &[u8]) -> Result<&'value Self> {
+  fn check(suspect: &MaybeUninit<Self>, _validator: &mut Validator) -> Result<()> {
     // Safe: There are no bitpattern validity requirements for Self
     let value = unsafe { suspect.assume_init_ref() };
 
@@ -41,13 +41,45 @@ impl View for Char {
       return Err(Error::Char { value: scalar });
     }
 
-    Ok(value)
+    Ok(())
+  }
+
+  fn check_slice(suspects: &[MaybeUninit<Self>], _validator: &mut Validator) -> Result<()> {
+    // Safe: There are no bitpattern validity requirements for Self
+    let chars = unsafe { slice::from_raw_parts(suspects.as_ptr() as *const Char, suspects.len()) };
+
+    // Check blocks without branching, so the check can be vectorized, and
+    // only search for the invalid char in a block which contains one:
+    for (block, chars) in chars.chunks(64).enumerate() {
+      if chars
+        .iter()
+        .fold(true, |valid, char| valid & char.is_valid())
+      {
+        continue;
+      }
+
+      for (index, char) in chars.iter().enumerate() {
+        if !char.is_valid() {
+          return Err(
+            Error::Char {
+              value: char.scalar(),
+            }
+            .at(Segment::Index(block * 64 + index)),
+          );
+        }
+      }
+    }
+
+    Ok(())
   }
 }
 
 impl FromView for char {
   fn from_view(view: &Self::View) -> Self {
-    char::from_u32(view.scalar()).unwrap()
+    // Safe because references to a `Char` are only handed out after `check`
+    // has verified that it is a valid char. Chars are in-line, so shallow
+    // checks verify this too.
+    unsafe { char::from_u32_unchecked(view.scalar()) }
   }
 }
 
@@ -55,6 +87,13 @@ impl Char {
   fn scalar(&self) -> u32 {
     u32::from_le_bytes([self.le_bytes[0], self.le_bytes[1], self.le_bytes[2], 0])
   }
+
+  /// Equivalent to `char::from_u32(self.scalar()).is_some()`, but without
+  /// branches.
+  fn is_valid(&self) -> bool {
+    let scalar = self.scalar();
+    (scalar < 0xD800) | ((scalar > 0xDFFF) & (scalar <= 0x10FFFF))
+  }
 }
 
 pub struct CharSerializer<A: Allocator, C: Continuation<A>> {
@@ -94,4 +133,35 @@ mod tests {
       value: 0xFFFFFF,
     });
   }
+
+  #[test]
+  fn is_valid() {
+    // Scalars around each boundary between valid and invalid ranges, and at the
+    // ends of the three byte range:
+    let scalars = [0, 0xD800, 0xE000, 0x110000, 1 << 24]
+      .iter()
+      .flat_map(|&boundary: &u32| boundary.saturating_sub(256)..(boundary + 256).min(1 << 24));
+
+    for scalar in scalars {
+      let bytes = u32::to_le_bytes(scalar);
+      let char = Char {
+        le_bytes: [bytes[0], bytes[1], bytes[2]],
+      };
+      assert_eq!(char.is_valid(), char::from_u32(scalar).is_some());
+    }
+  }
+
+  #[test]
+  fn check_slice() {
+    let mut buffer = vec!['a'; 200].serialize_to_vec().unwrap();
+    <Vec<char>>::view(&buffer).unwrap();
+
+    // Surrogate at index 130
+    buffer[16 + 130 * 3..16 + 131 * 3].copy_from_slice(&[0x00, 0xD8, 0x00]);
+
+    assert_eq!(
+      <Vec<char>>::view(&buffer).err().unwrap(),
+      Error::Char { value: 0xD800 }.at(Segment::Index(130))
+    );
+  }
 }
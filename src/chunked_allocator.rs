@@ -0,0 +1,127 @@
+use crate::common::*;
+
+/// Default size of each chunk, in bytes.
+const CHUNK_SIZE: usize = 1 << 20;
+
+/// Serializes into a sequence of fixed-size chunks.
+///
+/// Unlike `VecAllocator`, which reallocates and copies the message as it grows,
+/// bytes are written directly into their final chunk and never moved. This
+/// makes it a good choice for very large messages, which can then be written
+/// out with vectored writes.
+pub struct ChunkedAllocator {
+  chunk_size: usize,
+  chunks:     Vec<Box<[u8]>>,
+}
+
+impl ChunkedAllocator {
+  pub fn new() -> ChunkedAllocator {
+    Self::with_chunk_size(CHUNK_SIZE)
+  }
+
+  pub fn with_chunk_size(chunk_size: usize) -> ChunkedAllocator {
+    assert!(chunk_size > 0, "Chunk size must be greater than zero.");
+
+    Self {
+      chunks: Vec::new(),
+      chunk_size,
+    }
+  }
+}
+
+impl Default for ChunkedAllocator {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Allocator for ChunkedAllocator {
+  type Error = LengthMismatch;
+  type Output = Rope;
+  type Stack = VecStack;
+
+  fn write(&mut self, mut bytes: &[u8], mut offset: usize) -> Result<(), Self::Error> {
+    while !bytes.is_empty() {
+      let index = offset / self.chunk_size;
+      let start = offset % self.chunk_size;
+
+      while self.chunks.len() <= index {
+        self
+          .chunks
+          .push(vec![0; self.chunk_size].into_boxed_slice());
+      }
+
+      let len = bytes.len().min(self.chunk_size - start);
+
+      self.chunks[index][start..start + len].copy_from_slice(&bytes[..len]);
+
+      bytes = &bytes[len..];
+      offset += len;
+    }
+
+    Ok(())
+  }
+
+  fn finish(self, end: usize) -> Result<Self::Output, Self::Error> {
+    assert_eq!(self.chunks.len(), end.div_ceil(self.chunk_size));
+
+    Ok(Rope::new(self.chunks, end))
+  }
+
+  fn length_mismatch(&mut self, error: LengthMismatch) -> Self::Error {
+    error
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn case<N: X>(chunk_size: usize, native: N) {
+    let rope = N::store(ChunkedAllocator::with_chunk_size(chunk_size))
+      .serialize(&native)
+      .done()
+      .unwrap();
+
+    let want = native.serialize_to_vec().unwrap();
+
+    assert_eq!(rope.len(), want.len());
+
+    for chunk in rope.chunks() {
+      assert!(!chunk.is_empty());
+      assert!(chunk.len() <= chunk_size);
+    }
+
+    assert_eq!(rope.chunks().flatten().copied().collect::<Vec<u8>>(), want);
+
+    #[cfg(feature = "std")]
+    assert_eq!(
+      rope
+        .io_slices()
+        .iter()
+        .map(|slice| slice.len())
+        .sum::<usize>(),
+      want.len()
+    );
+
+    assert_eq!(rope.into_contiguous(), want);
+  }
+
+  #[test]
+  fn empty() {
+    case(1, ());
+  }
+
+  #[test]
+  fn single_chunk() {
+    case(64, vec![1u8, 2, 3]);
+  }
+
+  #[test]
+  fn multiple_chunks() {
+    case(1, vec![1u8, 2, 3]);
+    case(3, String::from("hello, world"));
+    case(5, strings());
+    case(8, vec![1u64, 2, 3]);
+  }
+}
@@ -1,37 +1,52 @@
 // core
 pub(crate) use core::{
   borrow::Borrow,
+  cell::Cell,
   char,
   convert::TryInto,
+  fmt::{self, Debug, Display, Formatter},
+  iter::FromIterator,
   marker::PhantomData,
   mem::{self, MaybeUninit},
-  ops::Range,
+  ops::{Deref, Range},
   ptr, slice,
   str::{self, Utf8Error},
 };
 
 // dependencies
-pub(crate) use static_assertions::const_assert;
+pub(crate) use static_assertions::{assert_impl_all, const_assert};
 
 // traits
 pub(crate) use crate::{
-  allocator::Allocator, continuation::Continuation, from_view::FromView, is::Is,
+  allocator::Allocator, continuation::Continuation, digest::Digest, from_view::FromView, is::Is,
   maybe_uninit_ext::MaybeUninitExt, range_ext::RangeExt, serialize::Serialize,
-  serializer::Serializer, to_i64::ToI64, to_u64::ToU64, view::View, x::X,
+  serializer::Serializer, stack::Stack, to_i64::ToI64, to_u64::ToU64, view::View, x::X,
 };
 
 // structs and enums
 pub(crate) use crate::{
+  array_stack::{ArrayStack, DEFAULT_DEPTH},
+  bit_slice::{BitSlice, Bits},
+  depth_exceeded::DepthExceeded,
   done::Done,
   error::Error,
+  fallible_slice_allocator::FallibleSliceAllocator,
   integer::{I64Serializer, U64Serializer, I64, U64},
+  length_mismatch::LengthMismatch,
+  limit_error::LimitError,
+  limited::Limited,
+  load_options::LoadOptions,
   offset::Offset,
+  overflow::Overflow,
   padding_serializer::PaddingSerializer,
+  segment::Segment,
+  size_allocator::SizeAllocator,
   slice::{Slice, SliceSerializer},
   slice_allocator::SliceAllocator,
   state::State,
   str::{Str, StrSerializer},
   usize::Usize,
+  validator::Validator,
 };
 
 // type aliases
@@ -40,15 +55,23 @@ pub(crate) use crate::Result;
 #[cfg(feature = "alloc")]
 mod alloc {
   // dependencies
-  pub(crate) use ::alloc::{collections::TryReserveError, string::String, vec::Vec};
+  pub(crate) use ::alloc::{
+    boxed::Box,
+    collections::TryReserveError,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+  };
 
   // traits
-  pub(crate) use crate::vec_ext::VecExt;
+  pub(crate) use crate::{storage::Storage, vec_ext::VecExt};
 
   // structs and enums
-  pub(crate) use crate::vec_allocator::VecAllocator;
+  pub(crate) use crate::{
+    bit_vec::BitVec, reserve_error::ReserveError, rope::Rope, vec_allocator::VecAllocator,
+    vec_stack::VecStack,
+  };
 
-  #[cfg(test)]
   pub(crate) use ::alloc::vec;
 }
 
@@ -57,17 +80,34 @@ pub(crate) use self::alloc::*;
 
 #[cfg(feature = "std")]
 mod std {
-  pub(crate) use std::io::{self, Seek, SeekFrom, Write};
+  pub(crate) use std::{
+    fs::File,
+    io::{self, IoSlice, Seek, SeekFrom, Write},
+  };
 }
 
 #[cfg(feature = "std")]
 pub(crate) use self::std::*;
 
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+pub(crate) use memmap2::{Mmap, MmapMut};
+
+#[cfg(all(feature = "std", feature = "rayon"))]
+pub(crate) use {
+  crate::shared_totals::SharedTotals,
+  core::sync::atomic::{AtomicUsize, Ordering},
+  rayon::prelude::*,
+};
+
+#[cfg(all(feature = "std", feature = "tokio"))]
+pub(crate) use tokio::io::{AsyncSeek, AsyncSeekExt, AsyncWrite, AsyncWriteExt};
+
+#[cfg(all(feature = "std", feature = "tokio"))]
+pub(crate) use crate::tokio_allocator::TokioWrites;
+
 #[cfg(test)]
 mod test {
-  pub(crate) use core::fmt::Debug;
-
-  pub(crate) use crate::test::{err, ok, ok_serialize};
+  pub(crate) use crate::test::{err, ok, ok_serialize, round_trip, strings, Lying};
 }
 
 #[cfg(test)]
@@ -0,0 +1,6 @@
+/// Error returned when pushing a region onto a full `Stack`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DepthExceeded {
+  /// Capacity of the stack.
+  pub depth: usize,
+}
@@ -0,0 +1,17 @@
+/// A hash function which is fed its input incrementally, used by
+/// `HashingAllocator` to compute the digest of a message as it is serialized.
+///
+/// A message is passed to `update` in pieces, split wherever its bytes happen
+/// to be committed, so the output of `finalize` must depend only on the bytes
+/// passed to `update`, in order, and not on how they were split. Streaming
+/// hash functions, such as implementations of `digest::Digest`, can be
+/// adapted with a wrapper which forwards to their own `update` and `finalize`.
+pub trait Digest {
+  type Output;
+
+  /// Hash `bytes`, which follow those passed to previous calls.
+  fn update(&mut self, bytes: &[u8]);
+
+  /// Return the digest of all bytes passed to `update`.
+  fn finalize(self) -> Self::Output;
+}
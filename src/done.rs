@@ -10,7 +10,7 @@ impl<A: Allocator> Done<A> {
   // 1.48.0-nightly (a1947b3f9 2020-09-10). It should be removed once the ICE is
   // fixed.
   #[inline(always)]
-  pub fn done(self) -> A::Output {
+  pub fn done(self) -> Result<A::Output, A::Error> {
     self.state.finish()
   }
 }
@@ -22,3 +22,69 @@ impl<A: Allocator> Continuation<A> for Done<A> {
     Self { state }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use core::cell::Cell;
+
+  /// Allocator which fails on the first write, and counts calls
+  struct Failing<'a> {
+    calls: &'a Cell<usize>,
+  }
+
+  impl<'a> Allocator for Failing<'a> {
+    type Error = &'static str;
+    type Output = ();
+    type Stack = VecStack;
+
+    fn write(&mut self, _bytes: &[u8], _offset: usize) -> Result<(), Self::Error> {
+      self.calls.set(self.calls.get() + 1);
+      Err("failed")
+    }
+
+    fn commit(&mut self, _offset: usize) -> Result<(), Self::Error> {
+      self.calls.set(self.calls.get() + 1);
+      Ok(())
+    }
+
+    fn finish(self, _end: usize) -> Result<Self::Output, Self::Error> {
+      self.calls.set(self.calls.get() + 1);
+      Ok(())
+    }
+
+    fn length_mismatch(&mut self, _error: LengthMismatch) -> Self::Error {
+      "length mismatch"
+    }
+  }
+
+  fn store<A: Allocator>(allocator: A) -> Result<A::Output, A::Error> {
+    let output = <Vec<String>>::store(allocator)
+      .serialize(&strings())
+      .done()?;
+
+    Ok(output)
+  }
+
+  #[test]
+  fn propagate() {
+    let mut buffer = [0; 1024];
+    assert!(store(FallibleSliceAllocator::new(&mut buffer)).is_ok());
+
+    let mut buffer = [0; 16];
+    assert_eq!(
+      store(FallibleSliceAllocator::new(&mut buffer)),
+      Err(Overflow::Buffer { needed: 54 })
+    );
+  }
+
+  #[test]
+  fn early_exit() {
+    let calls = Cell::new(0);
+
+    assert_eq!(store(Failing { calls: &calls }), Err("failed"));
+
+    assert_eq!(calls.get(), 1);
+  }
+}
@@ -1,12 +1,20 @@
 use crate::common::*;
 
-// Document that this is load errors only.
+// Document that this is load errors only, apart from `LengthMismatch`.
 // TODO: lint that all variants are documented?
+//
+// Positions are byte offsets from the start of the buffer, and buffers are
+// described by their length.
+//
+// Variants may be added in future versions, and `Path` is only present with
+// `alloc`, so matches must include a wildcard arm.
 #[derive(Debug, PartialEq)]
+#[non_exhaustive]
 pub enum Error {
+  // offset is not within the buffer, and may start before it
   OffsetBounds {
-    buffer: Range<*const u8>,
-    offset: Range<*const u8>,
+    buffer: usize,
+    offset: Range<isize>,
   },
   // offset is zero where a zero offset is not valid
   OffsetNull,
@@ -14,15 +22,16 @@ pub enum Error {
   OffsetValue {
     value: usize,
   },
-  // offset pointer + element size wraps memory space
+  // end of elements pointed to by offset overflows isize
   OffsetWrap {
-    start: *const u8,
-    end:   *const u8,
+    offset: usize,
+    value:  usize,
+    length: usize,
   },
-  // offset points before the beginning of the buffer or past the end of the buffer
+  // offset points past the end of the buffer
   OffsetElementBounds {
-    buffer:   Range<*const u8>,
-    elements: Range<*const u8>,
+    buffer:   usize,
+    elements: Range<isize>,
   },
   Usize {
     value: u64,
@@ -35,17 +44,115 @@ pub enum Error {
   Bool {
     value: u8,
   },
+  // unused trailing bits in the last byte of a bit slice are not zero
+  BitSlicePadding {
+    value: u8,
+  },
   Char {
     value: u32,
   },
   String {
     error: Utf8Error,
   },
+  // value passed to `Message::new` serialized the wrong number of elements
+  LengthMismatch {
+    error: LengthMismatch,
+  },
   Discriminant {
     value:   u8,
     maximum: u8,
     ty:      &'static str,
   },
+  // following an offset would exceed LoadOptions::max_depth
+  DepthLimit {
+    maximum: usize,
+  },
+  // total elements pointed to by offsets would exceed LoadOptions::max_elements
+  ElementLimit {
+    maximum: usize,
+  },
+  // total bytes pointed to by offsets would exceed LoadOptions::max_bytes
+  ByteLimit {
+    maximum: usize,
+  },
+  // out-of-line data does not immediately follow the previous out-of-line data
+  CanonicalOffset {
+    expected: usize,
+    actual:   usize,
+  },
+  // padding byte is not zero
+  Padding {
+    value: u8,
+  },
+  // buffer continues past the end of the message
+  TrailingBytes {
+    end: usize,
+  },
+  // error inside a field or element, with the path to it, from the innermost
+  // segment outwards, since segments are added as the error is returned
+  #[cfg(feature = "alloc")]
+  Path {
+    path:  Vec<Segment>,
+    error: Box<Error>,
+  },
+}
+
+impl Error {
+  /// Prefix the path at which this error occurred with `segment`. Paths are
+  /// only recorded with `alloc`, without which the error is returned
+  /// unchanged.
+  pub fn at(self, segment: Segment) -> Error {
+    #[cfg(feature = "alloc")]
+    match self {
+      Error::Path { mut path, error } => {
+        path.push(segment);
+        Error::Path { path, error }
+      },
+      error => Error::Path {
+        path:  vec![segment],
+        error: Box::new(error),
+      },
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    self
+  }
+
+  /// Add `offset` to the index at the start of this error's path, for errors
+  /// in a subslice which starts at `offset`.
+  #[cfg(all(feature = "std", feature = "rayon"))]
+  pub(crate) fn rebase(self, offset: usize) -> Error {
+    match self {
+      Error::Path { mut path, error } => {
+        if let Some(Segment::Index(index)) = path.last_mut() {
+          *index += offset;
+        }
+        Error::Path { path, error }
+      },
+      error => error,
+    }
+  }
+
+  /// The path from the root of the message to the view in which this error
+  /// occurred.
+  pub fn path(&self) -> impl DoubleEndedIterator<Item = Segment> + '_ {
+    let path: &[Segment] = match self {
+      #[cfg(feature = "alloc")]
+      Error::Path { path, .. } => path,
+      _ => &[],
+    };
+
+    path.iter().rev().copied()
+  }
+
+  /// This error, without its path.
+  pub fn kind(&self) -> &Error {
+    match self {
+      #[cfg(feature = "alloc")]
+      Error::Path { error, .. } => error,
+      error => error,
+    }
+  }
 }
 
 impl From<Utf8Error> for Error {
@@ -53,3 +160,158 @@ impl From<Utf8Error> for Error {
     Error::String { error }
   }
 }
+
+impl From<LengthMismatch> for Error {
+  fn from(error: LengthMismatch) -> Self {
+    Error::LengthMismatch { error }
+  }
+}
+
+impl Display for Error {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Error::OffsetBounds { buffer, offset } => write!(
+        f,
+        "Offset at {}..{} is not within buffer of length {}",
+        offset.start, offset.end, buffer
+      ),
+      Error::OffsetNull => write!(f, "Offset is null"),
+      Error::OffsetValue { value } =>
+        write!(f, "Offset value {} points into the offset itself", value),
+      Error::OffsetWrap {
+        offset,
+        value,
+        length,
+      } => write!(
+        f,
+        "Offset at {} with value {} to {} elements overflows",
+        offset, value, length
+      ),
+      Error::OffsetElementBounds { buffer, elements } => write!(
+        f,
+        "Elements at {}..{} are not within buffer of length {}",
+        elements.start, elements.end, buffer
+      ),
+      Error::Usize { value } => write!(f, "Usize value {} does not fit in a usize", value),
+      Error::Isize { value } => write!(f, "Isize value {} does not fit in an isize", value),
+      Error::BufferTooSmall => write!(f, "Buffer is too small to contain the root view"),
+      Error::Bool { value } => write!(f, "Invalid bool value {:#04x}", value),
+      Error::BitSlicePadding { value } => write!(
+        f,
+        "Unused trailing bits of bit slice are not zero in last byte {:#010b}",
+        value
+      ),
+      Error::Char { value } => write!(f, "Invalid char value {:#x}", value),
+      Error::String { error } => write!(f, "Invalid UTF-8 in string: {}", error),
+      Error::LengthMismatch { error } => write!(f, "Message could not be serialized: {}", error),
+      Error::Discriminant { value, maximum, ty } => write!(
+        f,
+        "Invalid discriminant {} for {}, maximum is {}",
+        value, ty, maximum
+      ),
+      Error::DepthLimit { maximum } =>
+        write!(f, "Message nesting exceeds depth limit of {}", maximum),
+      Error::ElementLimit { maximum } => write!(
+        f,
+        "Message contains more than the limit of {} elements",
+        maximum
+      ),
+      Error::ByteLimit { maximum } => write!(
+        f,
+        "Message contains more than the limit of {} bytes",
+        maximum
+      ),
+      Error::CanonicalOffset { expected, actual } => write!(
+        f,
+        "Out-of-line data starts at {}, but canonically starts at {}",
+        actual, expected
+      ),
+      Error::Padding { value } => write!(f, "Padding byte {:#04x} is not zero", value),
+      Error::TrailingBytes { end } => write!(f, "Buffer continues past end of message at {}", end),
+      #[cfg(feature = "alloc")]
+      Error::Path { error, .. } => {
+        write!(f, "{} at root", error)?;
+        for segment in self.path() {
+          write!(f, "{}", segment)?;
+        }
+        Ok(())
+      },
+    }
+  }
+}
+
+// Errors contain no pointers, and so can be sent between threads:
+assert_impl_all!(Error: Send, Sync);
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match self {
+      Error::String { error } => Some(error),
+      Error::LengthMismatch { error } => Some(error),
+      Error::Path { error, .. } => error.source(),
+      _ => None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn display() {
+    assert_eq!(Error::OffsetNull.to_string(), "Offset is null");
+    assert_eq!(
+      Error::Bool { value: 2 }.to_string(),
+      "Invalid bool value 0x02"
+    );
+    assert_eq!(
+      Error::Discriminant {
+        value:   3,
+        maximum: 1,
+        ty:      "Option",
+      }
+      .to_string(),
+      "Invalid discriminant 3 for Option, maximum is 1"
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "std")]
+  fn source() {
+    use std::error::Error as _;
+
+    let error = Error::from(String::from_utf8(vec![0xFF]).unwrap_err().utf8_error());
+
+    assert_eq!(
+      error.to_string(),
+      "Invalid UTF-8 in string: invalid utf-8 sequence of 1 bytes from index 0"
+    );
+
+    assert!(error.source().is_some());
+    assert!(error.at(Segment::Index(0)).source().is_some());
+    assert!(Error::OffsetNull.source().is_none());
+  }
+
+  #[test]
+  fn path() {
+    let error = Error::Bool { value: 2 }
+      .at(Segment::Field("name"))
+      .at(Segment::Index(17))
+      .at(Segment::Field("items"));
+
+    assert_eq!(error.path().collect::<Vec<Segment>>(), &[
+      Segment::Field("items"),
+      Segment::Index(17),
+      Segment::Field("name"),
+    ]);
+
+    assert_eq!(error.kind(), &Error::Bool { value: 2 });
+
+    assert_eq!(
+      error.to_string(),
+      "Invalid bool value 0x02 at root.items[17].name"
+    );
+  }
+}
@@ -0,0 +1,176 @@
+use crate::common::*;
+
+/// Serializes into a fixed-size buffer, returning an error instead of panicking
+/// if the message does not fit, is nested more than `DEPTH` levels deep, or a
+/// slice or array serializer is given the wrong number of elements.
+pub struct FallibleSliceAllocator<'slice, const DEPTH: usize = DEFAULT_DEPTH> {
+  slice: &'slice mut [u8],
+}
+
+impl<'slice> FallibleSliceAllocator<'slice> {
+  pub fn new(slice: &'slice mut [u8]) -> FallibleSliceAllocator<'slice> {
+    Self::with_depth(slice)
+  }
+}
+
+impl<'slice, const DEPTH: usize> FallibleSliceAllocator<'slice, DEPTH> {
+  pub fn with_depth(slice: &'slice mut [u8]) -> FallibleSliceAllocator<'slice, DEPTH> {
+    Self { slice }
+  }
+}
+
+impl<'slice, const DEPTH: usize> Allocator for FallibleSliceAllocator<'slice, DEPTH> {
+  type Error = Overflow;
+  type Output = &'slice [u8];
+  type Stack = ArrayStack<DEPTH>;
+
+  fn write(&mut self, bytes: &[u8], offset: usize) -> Result<(), Self::Error> {
+    // If the write doesn't fit, ignore it. The message end will be past the end
+    // of the slice, and the overflow will be reported by `finish`, along with
+    // the number of bytes the whole message needs.
+    let end = match offset.checked_add(bytes.len()) {
+      Some(end) if end <= self.slice.len() => end,
+      _ => return Ok(()),
+    };
+
+    self.slice[offset..end].copy_from_slice(bytes);
+
+    Ok(())
+  }
+
+  fn finish(self, end: usize) -> Result<Self::Output, Self::Error> {
+    if end > self.slice.len() {
+      return Err(Overflow::Buffer { needed: end });
+    }
+
+    Ok(&self.slice[..end])
+  }
+
+  fn depth_exceeded(&mut self, error: DepthExceeded) -> Result<(), Self::Error> {
+    Err(Overflow::Stack { depth: error.depth })
+  }
+
+  fn length_mismatch(&mut self, error: LengthMismatch) -> Self::Error {
+    Overflow::Mismatch(error)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn fits() {
+    let mut buffer = [0xFF; 4];
+
+    let have = u16::store(FallibleSliceAllocator::new(&mut buffer))
+      .serialize(&0x0201u16)
+      .done();
+
+    assert_eq!(have, Ok(&[1, 2][..]));
+  }
+
+  #[test]
+  fn fits_exactly() {
+    let mut buffer = [0; 18];
+
+    let have = "hi"
+      .serialize(<&str>::store(FallibleSliceAllocator::new(&mut buffer)))
+      .done();
+
+    #[rustfmt::skip]
+    assert_eq!(have, Ok(&[
+      // offset
+      16, 0, 0, 0, 0, 0, 0, 0,
+      // length
+      2, 0, 0, 0, 0, 0, 0, 0,
+      // contents
+      104, 105,
+    ][..]));
+  }
+
+  #[test]
+  fn overflow() {
+    let mut buffer = [0; 17];
+
+    let have = "hi"
+      .serialize(<&str>::store(FallibleSliceAllocator::new(&mut buffer)))
+      .done();
+
+    assert_eq!(have, Err(Overflow::Buffer { needed: 18 }));
+  }
+
+  #[test]
+  fn store_to_slice() {
+    let mut buffer = [0; 17];
+
+    let have = "hi".serialize(<&str>::store_to_slice(&mut buffer)).done();
+
+    assert_eq!(have, Err(Overflow::Buffer { needed: 18 }));
+  }
+
+  #[test]
+  fn overflow_root() {
+    let mut buffer = [0; 3];
+
+    let have = u32::store(FallibleSliceAllocator::new(&mut buffer))
+      .serialize(&0u32)
+      .done();
+
+    assert_eq!(have, Err(Overflow::Buffer { needed: 4 }));
+  }
+
+  #[test]
+  fn depth_exceeded() {
+    let mut buffer = [0; 1024];
+
+    let have = <Vec<Vec<Vec<u8>>>>::store(FallibleSliceAllocator::<3>::with_depth(&mut buffer))
+      .serialize(&vec![vec![vec![1u8]]])
+      .done();
+
+    assert_eq!(have, Err(Overflow::Stack { depth: 3 }));
+
+    let have = <Vec<Vec<Vec<u8>>>>::store(FallibleSliceAllocator::<4>::with_depth(&mut buffer))
+      .serialize(&vec![vec![vec![1u8]]])
+      .done();
+
+    assert_eq!(
+      have.unwrap(),
+      vec![vec![vec![1u8]]].serialize_to_vec().unwrap().as_slice()
+    );
+  }
+
+  #[test]
+  fn length_mismatch() {
+    let mut buffer = [0; 1024];
+
+    let have = <Vec<u8>>::store(FallibleSliceAllocator::new(&mut buffer))
+      .serialize_iterator(Lying {
+        inner:  [1u8, 2].iter(),
+        length: 3,
+      })
+      .done();
+
+    assert_eq!(
+      have,
+      Err(Overflow::Mismatch(LengthMismatch {
+        expected: 3,
+        actual:   2,
+      }))
+    );
+  }
+
+  #[test]
+  fn write_past_end_of_address_space() {
+    let mut buffer = [0; 4];
+
+    let mut allocator = FallibleSliceAllocator::new(&mut buffer);
+
+    assert_eq!(allocator.write(&[1, 2], usize::MAX), Ok(()));
+
+    assert_eq!(
+      allocator.finish(usize::MAX),
+      Err(Overflow::Buffer { needed: usize::MAX })
+    );
+  }
+}
@@ -2,8 +2,7 @@ use crate::common::*;
 
 #[derive(Default)]
 pub struct FallibleVecAllocator {
-  vec:   Vec<u8>,
-  error: Option<TryReserveError>,
+  vec: Vec<u8>,
 }
 
 impl FallibleVecAllocator {
@@ -13,13 +12,11 @@ impl FallibleVecAllocator {
 }
 
 impl Allocator for FallibleVecAllocator {
-  type Output = Result<Vec<u8>, TryReserveError>;
-
-  fn write(&mut self, bytes: &[u8], offset: usize) {
-    if self.error.is_some() {
-      return;
-    }
+  type Error = ReserveError;
+  type Output = Vec<u8>;
+  type Stack = VecStack;
 
+  fn write(&mut self, bytes: &[u8], offset: usize) -> Result<(), Self::Error> {
     // Calculate total number of bytes:
     let end = offset + bytes.len();
 
@@ -28,30 +25,26 @@ impl Allocator for FallibleVecAllocator {
       // calculate additional number of bytes,
       let additional = end - self.vec.len();
 
-      // and try to extend self.vec by that number of bytes.
-      if let Err(error) = self.vec.try_reserve(additional) {
-        // If an error occured, drop current allocation to help alleviate memory
-        // pressure,
-        self.vec = Vec::new();
-
-        // save the error,
-        self.error = Some(error);
-
-        // and return. All future writes will be ignored.
-        return;
-      }
+      // and try to extend self.vec by that number of bytes. If an error
+      // occurs, no further writes will be made, and the allocator and its
+      // current allocation will be dropped.
+      self
+        .vec
+        .try_reserve(additional)
+        .map_err(ReserveError::Capacity)?;
     }
 
     self.vec.place(bytes, offset);
+
+    Ok(())
   }
 
-  fn finish(self, end: usize) -> Self::Output {
-    match self.error {
-      None => {
-        assert_eq!(self.vec.len(), end);
-        Ok(self.vec)
-      },
-      Some(error) => Err(error),
-    }
+  fn finish(self, end: usize) -> Result<Self::Output, Self::Error> {
+    assert_eq!(self.vec.len(), end);
+    Ok(self.vec)
+  }
+
+  fn length_mismatch(&mut self, error: LengthMismatch) -> Self::Error {
+    ReserveError::Mismatch(error)
   }
 }
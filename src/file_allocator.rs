@@ -3,7 +3,6 @@ use crate::common::*;
 // TODO: document that anything that implements write and seek can be used
 pub struct FileAllocator<F: Write + Seek> {
   file:     F,
-  error:    Option<io::Error>,
   position: usize,
   end:      usize,
 }
@@ -11,7 +10,6 @@ pub struct FileAllocator<F: Write + Seek> {
 impl<F: Write + Seek> FileAllocator<F> {
   pub fn new(file: F) -> FileAllocator<F> {
     Self {
-      error: None,
       position: 0,
       end: 0,
       file,
@@ -20,40 +18,33 @@ impl<F: Write + Seek> FileAllocator<F> {
 }
 
 impl<F: Write + Seek> Allocator for FileAllocator<F> {
-  type Output = io::Result<()>;
-
-  fn write(&mut self, bytes: &[u8], offset: usize) {
-    if self.error.is_some() {
-      return;
-    }
+  type Error = io::Error;
+  type Output = ();
+  type Stack = VecStack;
 
+  fn write(&mut self, bytes: &[u8], offset: usize) -> Result<(), Self::Error> {
     if self.position != offset {
       // TODO: Fix this unwrap
       let seek_from = SeekFrom::Start(offset.try_into().unwrap());
 
-      if let Err(error) = self.file.seek(seek_from) {
-        self.error = Some(error);
-        return;
-      }
+      self.file.seek(seek_from)?;
     }
 
-    if let Err(error) = self.file.write_all(bytes) {
-      self.error = Some(error);
-      return;
-    }
+    self.file.write_all(bytes)?;
 
     self.position = offset + bytes.len();
     self.end = self.end.max(self.position);
+
+    Ok(())
   }
 
-  fn finish(self, end: usize) -> Self::Output {
-    match self.error {
-      None => {
-        assert_eq!(self.end, end);
-        Ok(())
-      },
-      Some(error) => Err(error),
-    }
+  fn finish(self, end: usize) -> Result<Self::Output, Self::Error> {
+    assert_eq!(self.end, end);
+    Ok(())
+  }
+
+  fn length_mismatch(&mut self, error: LengthMismatch) -> Self::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, error)
   }
 }
 
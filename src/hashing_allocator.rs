@@ -0,0 +1,178 @@
+use crate::common::*;
+
+/// Wraps another allocator, hashing the bytes of the message as they are
+/// written, so that a digest can be computed without reading the message back.
+///
+/// Writes arrive out of order, so bytes are kept in memory until they are
+/// committed, at which point they are passed to the digest in order, and
+/// released.
+pub struct HashingAllocator<A: Allocator, D: Digest> {
+  inner:  A,
+  digest: D,
+  // Bytes of the message starting at `hashed`
+  window: Vec<u8>,
+  hashed: usize,
+}
+
+impl<A: Allocator, D: Digest> HashingAllocator<A, D> {
+  pub fn new(inner: A, digest: D) -> HashingAllocator<A, D> {
+    Self {
+      window: Vec::new(),
+      hashed: 0,
+      inner,
+      digest,
+    }
+  }
+
+  /// Hash all bytes before `offset`.
+  fn hash(&mut self, offset: usize) {
+    let hashable = offset - self.hashed;
+    self.digest.update(&self.window[..hashable]);
+    self.window.drain(..hashable);
+    self.hashed = offset;
+  }
+}
+
+impl<A: Allocator, D: Digest> Allocator for HashingAllocator<A, D> {
+  type Error = A::Error;
+  type Output = (A::Output, D::Output);
+  type Stack = A::Stack;
+
+  fn write(&mut self, bytes: &[u8], offset: usize) -> Result<(), Self::Error> {
+    assert!(offset >= self.hashed);
+
+    self.window.place(bytes, offset - self.hashed);
+
+    self.inner.write(bytes, offset)
+  }
+
+  fn commit(&mut self, offset: usize) -> Result<(), Self::Error> {
+    self.hash(offset);
+    self.inner.commit(offset)
+  }
+
+  fn finish(mut self, end: usize) -> Result<Self::Output, Self::Error> {
+    assert_eq!(self.hashed + self.window.len(), end);
+
+    self.hash(end);
+
+    let output = self.inner.finish(end)?;
+
+    Ok((output, self.digest.finalize()))
+  }
+
+  fn stack(&mut self) -> Self::Stack {
+    self.inner.stack()
+  }
+
+  fn reclaim(&mut self, stack: Self::Stack) {
+    self.inner.reclaim(stack)
+  }
+
+  fn depth_exceeded(&mut self, error: DepthExceeded) -> Result<(), Self::Error> {
+    self.inner.depth_exceeded(error)
+  }
+
+  fn length_mismatch(&mut self, error: LengthMismatch) -> Self::Error {
+    self.inner.length_mismatch(error)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// 64-bit FNV-1a, which hashes one byte at a time
+  struct Fnv(u64);
+
+  impl Fnv {
+    fn new() -> Fnv {
+      Fnv(0xcbf29ce484222325)
+    }
+  }
+
+  impl Digest for Fnv {
+    type Output = u64;
+
+    fn update(&mut self, bytes: &[u8]) {
+      for byte in bytes {
+        self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x100000001b3);
+      }
+    }
+
+    fn finalize(self) -> u64 {
+      self.0
+    }
+  }
+
+  /// Digest which returns the bytes passed to each call to `update`
+  #[derive(Default)]
+  struct Recorder(Vec<Vec<u8>>);
+
+  impl Digest for Recorder {
+    type Output = Vec<Vec<u8>>;
+
+    fn update(&mut self, bytes: &[u8]) {
+      self.0.push(bytes.to_vec());
+    }
+
+    fn finalize(self) -> Vec<Vec<u8>> {
+      self.0
+    }
+  }
+
+  fn case<N: X>(native: N) {
+    let (have, digest) = N::store(HashingAllocator::new(VecAllocator::new(), Fnv::new()))
+      .serialize(&native)
+      .done()
+      .unwrap();
+
+    let want = native.serialize_to_vec().unwrap();
+
+    assert_eq!(have, want);
+
+    let mut fnv = Fnv::new();
+    fnv.update(&want);
+
+    assert_eq!(digest, fnv.finalize());
+  }
+
+  #[test]
+  fn digest() {
+    case(());
+    case(1u64);
+    case(String::from("hello"));
+    case(strings());
+    case(vec![vec![1u8, 2], vec![], vec![3]]);
+  }
+
+  #[test]
+  fn split_writes() {
+    let native = vec![vec![1u8, 2], vec![], vec![3]];
+
+    let (have, updates) = <Vec<Vec<u8>>>::store(HashingAllocator::new(
+      VecAllocator::new(),
+      Recorder::default(),
+    ))
+    .serialize(&native)
+    .done()
+    .unwrap();
+
+    // The message is hashed in order, in more than one piece:
+    assert!(updates.len() > 1);
+    assert_eq!(updates.concat(), have);
+  }
+
+  #[test]
+  fn different() {
+    let digest = |native: &str| {
+      <&str>::store(HashingAllocator::new(SizeAllocator::new(), Fnv::new()))
+        .serialize(&native)
+        .done()
+        .unwrap()
+        .1
+    };
+
+    assert_ne!(digest("foo"), digest("bar"));
+  }
+}
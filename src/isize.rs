@@ -24,15 +24,15 @@ pub struct Isize {
   inner: I64,
 }
 
-impl View for Isize {
+unsafe impl View for Isize {
   type Serializer<A: Allocator, C: Continuation<A>> = IsizeSerializer<A, C>;
 
-  fn check<'value>(suspect: &'value MaybeUninit<Self>, buffer: &[u8]) -> Result<&'value Self> {
+  fn check(suspect: &MaybeUninit<Self>, validator: &mut Validator) -> Result<()> {
     let struct_pointer: *const Isize = suspect.as_ptr();
 
-    let inner_pointer = struct_pointer as *const U64;
+    let inner_pointer = struct_pointer as *const I64;
 
-    let inner_suspect_pointer = inner_pointer as *const MaybeUninit<U64>;
+    let inner_suspect_pointer = inner_pointer as *const MaybeUninit<I64>;
 
     // Safe because:
     // - Alignment is correct: `inner` is a view type, so it has alignment 1.
@@ -40,27 +40,25 @@ impl View for Isize {
     // - Valid bitpattern: All bitpatterns are valid for MaybeUninit.
     let inner_suspect = unsafe { &*inner_suspect_pointer };
 
-    View::check(inner_suspect, buffer)?;
-
-    // Safe because inner has been checked.
-    let reference = unsafe { suspect.assume_init_ref() };
+    let inner = inner_suspect.check(validator)?;
 
     // Enforce the additional invariant that Iszie values may not be larger than can
     // fit in the native `usize` type.
 
-    let value = reference.inner.to_native();
+    let value = inner.to_native();
 
     // TODO: There isn't a test for this
     if value < isize::MIN.to_i64() || value > isize::MAX.to_i64() {
       return Err(Error::Isize { value });
     }
 
-    Ok(reference)
+    Ok(())
   }
 }
 
 impl From<&Isize> for isize {
   fn from(view: &Isize) -> isize {
+    // Does not truncate, since `check` verified that the value fits in an isize.
     i64::from(&view.inner) as isize
   }
 }
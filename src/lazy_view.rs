@@ -0,0 +1,349 @@
+use crate::common::*;
+
+/// A view which has only been partially validated, for reading small parts of
+/// very large messages.
+///
+/// Loading a lazy view only checks the view itself, and that any offsets it
+/// contains point within the buffer, but not the data they point to. The rest
+/// of the message is checked as it is accessed, with `get`, `project`, and
+/// finally `validate`, which checks everything reachable from the view and
+/// returns it.
+///
+/// Once a view has passed `validate`, calling it again returns the view without
+/// checking it again, and views loaded from it with `get` and `project` are
+/// already known to be valid, so they are not checked at all.
+///
+/// Limits in the `LoadOptions` passed to `load_with` apply to each of these
+/// checks separately. Canonical encoding can only be checked for a whole
+/// message, so it is only checked when validating the root view.
+pub struct LazyView<'buffer, V: View> {
+  buffer:    &'buffer [u8],
+  options:   LoadOptions,
+  // Checked with a shallow validator, so only its in-line part is known to be
+  // valid, and it must not be exposed until it has passed a full check by
+  // `validate`
+  view:      &'buffer V,
+  // Set once `view` has passed a full check
+  validated: Cell<bool>,
+}
+
+impl<'buffer, V: View> LazyView<'buffer, V> {
+  pub fn load(buffer: &'buffer [u8]) -> Result<LazyView<'buffer, V>> {
+    Self::load_with(buffer, &LoadOptions::default())
+  }
+
+  pub fn load_with(buffer: &'buffer [u8], options: &LoadOptions) -> Result<LazyView<'buffer, V>> {
+    Self::new(buffer, *options, V::cast(buffer, 0)?)
+  }
+
+  fn new(
+    buffer: &'buffer [u8],
+    options: LoadOptions,
+    suspect: &'buffer MaybeUninit<V>,
+  ) -> Result<LazyView<'buffer, V>> {
+    let view = suspect.check(&mut Validator::shallow(buffer, &options))?;
+    Ok(Self {
+      buffer,
+      options,
+      view,
+      validated: Cell::new(false),
+    })
+  }
+
+  /// Load a view contained in this one, which has already passed a full check
+  /// along with this view, without checking it again.
+  fn validated<F: View>(&self, suspect: &'buffer MaybeUninit<F>) -> LazyView<'buffer, F> {
+    // Safe because checking this view fully also checked `suspect`
+    LazyView {
+      buffer:    self.buffer,
+      options:   self.inner_options(),
+      view:      unsafe { &*suspect.as_ptr() },
+      validated: Cell::new(true),
+    }
+  }
+
+  /// Options for views contained in this one, which are not the root of the
+  /// message, and so cannot be checked for canonical encoding.
+  fn inner_options(&self) -> LoadOptions {
+    LoadOptions {
+      canonical: false,
+      ..self.options
+    }
+  }
+
+  /// Check the out-of-line data reachable from this view, and return it. Only
+  /// the first successful call checks the view.
+  pub fn validate(&self) -> Result<&'buffer V> {
+    if self.validated.get() {
+      return Ok(self.view);
+    }
+
+    let suspect = unsafe { &*(self.view as *const V as *const MaybeUninit<V>) };
+
+    let mut validator = Validator::new(self.buffer, &self.options);
+
+    // Only the root view may be loaded with `canonical` set, and it occupies
+    // the start of the buffer:
+    validator.allocate(0..mem::size_of::<V>())?;
+
+    let view = suspect.check(&mut validator)?;
+
+    validator.finish()?;
+
+    self.validated.set(true);
+
+    Ok(view)
+  }
+
+  /// Lazily load a view contained in this one, such as a field of a struct,
+  /// using `field` to get a pointer to it from a pointer to this view:
+  ///
+  /// ```ignore
+  /// let items = lazy.project(|record| unsafe { &raw const (*record).items })?;
+  /// ```
+  ///
+  /// Panics if the returned pointer does not point within this view, or if `F`
+  /// does not have alignment 1.
+  pub fn project<F: View>(
+    &self,
+    field: impl FnOnce(*const V) -> *const F,
+  ) -> Result<LazyView<'buffer, F>> {
+    assert_eq!(
+      mem::align_of::<F>(),
+      1,
+      "View types must have alignment 1. Alignment is {}.",
+      mem::align_of::<F>(),
+    );
+
+    let start = self.view as *const V as usize;
+    let pointer = field(self.view);
+
+    assert!(
+      pointer as usize >= start
+        && pointer as usize + mem::size_of::<F>() <= start + mem::size_of::<V>(),
+      "LazyView::project: Projected pointer is not within view."
+    );
+
+    let suspect = unsafe { &*(pointer as *const MaybeUninit<F>) };
+
+    if self.validated.get() {
+      return Ok(self.validated(suspect));
+    }
+
+    LazyView::new(self.buffer, self.inner_options(), suspect)
+  }
+}
+
+impl<'buffer, V: View> LazyView<'buffer, Slice<V>> {
+  pub fn len(&self) -> usize {
+    self.view.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.view.is_empty()
+  }
+
+  /// Lazily load the element at `index`, or return `None` if `index` is out of
+  /// bounds.
+  pub fn get(&self, index: usize) -> Option<Result<LazyView<'buffer, V>>> {
+    if index >= self.len() {
+      return None;
+    }
+
+    // Safe because the slice's offset was checked to point to `len` elements
+    // within the buffer.
+    let suspect = unsafe { &*(self.view.as_ptr().add(index) as *const MaybeUninit<V>) };
+
+    if self.validated.get() {
+      return Some(Ok(self.validated(suspect)));
+    }
+
+    Some(
+      LazyView::new(self.buffer, self.inner_options(), suspect)
+        .map_err(|error| error.at(Segment::Index(index))),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn get() {
+    let mut buffer = vec![
+      String::from("foo"),
+      String::from("bar"),
+      String::from("baz"),
+    ]
+    .serialize_to_vec()
+    .unwrap();
+
+    // Corrupt the contents of the last string
+    let last = buffer.len() - 1;
+    buffer[last] = 0xFF;
+
+    assert!(<Vec<String>>::view(&buffer).is_err());
+
+    let lazy = LazyView::<Slice<Str>>::load(&buffer).unwrap();
+
+    assert_eq!(lazy.len(), 3);
+
+    let first = lazy.get(0).unwrap().unwrap().validate().unwrap();
+    assert_eq!(first.as_str(), "foo");
+
+    // The offset and length of the last string are valid, but its contents
+    // aren't:
+    let last = lazy.get(2).unwrap().unwrap();
+    assert!(last.validate().is_err());
+
+    assert!(lazy.validate().is_err());
+
+    assert!(lazy.get(3).is_none());
+  }
+
+  #[test]
+  fn validated() {
+    let buffer = [vec![1u8], vec![2u8]].serialize_to_vec().unwrap();
+
+    let lazy = LazyView::<[Slice<u8>; 2]>::load(&buffer).unwrap();
+
+    assert!(!lazy.validated.get());
+    assert!(!lazy
+      .project(|array| unsafe { &raw const (*array)[0] })
+      .unwrap()
+      .validated
+      .get());
+
+    let view = lazy.validate().unwrap();
+    assert!(lazy.validated.get());
+    assert!(ptr::eq(lazy.validate().unwrap(), view));
+
+    let first = lazy
+      .project(|array| unsafe { &raw const (*array)[0] })
+      .unwrap();
+    assert!(first.validated.get());
+    assert_eq!(first.validate().unwrap().as_slice(), &[1]);
+
+    let buffer = vec![vec![1u8], vec![2u8]].serialize_to_vec().unwrap();
+
+    let lazy = LazyView::<Slice<Slice<u8>>>::load(&buffer).unwrap();
+
+    assert!(!lazy.get(1).unwrap().unwrap().validated.get());
+
+    lazy.validate().unwrap();
+
+    let second = lazy.get(1).unwrap().unwrap();
+    assert!(second.validated.get());
+    assert_eq!(second.validate().unwrap().as_slice(), &[2]);
+  }
+
+  #[test]
+  fn shallow() {
+    // The offset of the second element points past the end of the buffer
+    let mut buffer = vec![vec![1u8], vec![2u8]].serialize_to_vec().unwrap();
+    buffer[32] = 0xFF;
+
+    let lazy = LazyView::<Slice<Slice<u8>>>::load(&buffer).unwrap();
+
+    lazy.get(0).unwrap().unwrap();
+
+    let error = lazy.get(1).unwrap().err().unwrap();
+    assert_eq!(error.path().collect::<Vec<Segment>>(), &[Segment::Index(1)]);
+  }
+
+  #[test]
+  fn load_with() {
+    let buffer = vec![vec![1u8, 2], vec![3u8]].serialize_to_vec().unwrap();
+
+    let options = LoadOptions {
+      max_elements: 2,
+      ..LoadOptions::default()
+    };
+
+    let lazy = LazyView::<Slice<Slice<u8>>>::load_with(&buffer, &options).unwrap();
+
+    assert_eq!(
+      lazy.get(0).unwrap().unwrap().validate().unwrap().as_slice(),
+      &[1, 2]
+    );
+
+    assert_eq!(
+      lazy.validate().err().unwrap().kind(),
+      &Error::ElementLimit { maximum: 2 }
+    );
+
+    let canonical = LoadOptions {
+      canonical: true,
+      ..LoadOptions::default()
+    };
+
+    let lazy = LazyView::<Slice<Slice<u8>>>::load_with(&buffer, &canonical).unwrap();
+    lazy.validate().unwrap();
+
+    let mut trailing = buffer.clone();
+    trailing.push(0);
+
+    let lazy = LazyView::<Slice<Slice<u8>>>::load_with(&trailing, &canonical).unwrap();
+
+    assert_eq!(lazy.validate().err().unwrap(), Error::TrailingBytes {
+      end: buffer.len(),
+    });
+
+    lazy.get(1).unwrap().unwrap().validate().unwrap();
+  }
+
+  #[test]
+  fn invalid_str() {
+    let mut buffer = String::from("foo").serialize_to_vec().unwrap();
+    buffer[16] = 0xFF;
+
+    // Loading only checks the string's offset and length:
+    let lazy = LazyView::<Str>::load(&buffer).unwrap();
+
+    // The only way to get a `&Str`, and call `as_str`, is to validate it:
+    assert_eq!(lazy.validate().err().unwrap(), Error::String {
+      error: str::from_utf8(&buffer[16..]).unwrap_err(),
+    });
+  }
+
+  #[test]
+  fn project() {
+    let buffer = [String::from("foo"), String::from("bar")]
+      .serialize_to_vec()
+      .unwrap();
+
+    let lazy = LazyView::<[Str; 2]>::load(&buffer).unwrap();
+
+    let string = lazy
+      .project(|array| unsafe { &raw const (*array)[1] })
+      .unwrap();
+
+    assert_eq!(string.validate().unwrap().as_str(), "bar");
+  }
+
+  #[test]
+  #[should_panic(expected = "LazyView::project: Projected pointer is not within view.")]
+  fn project_outside_view() {
+    let buffer = [0u8, 1].serialize_to_vec().unwrap();
+
+    let lazy = LazyView::<[u8; 1]>::load(&buffer).unwrap();
+
+    lazy
+      .project(|array| unsafe { (array as *const u8).add(1) })
+      .ok();
+  }
+
+  #[test]
+  fn root_offset_bounds() {
+    let buffer = [16, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0];
+
+    assert_eq!(
+      LazyView::<Slice<u8>>::load(&buffer).err().unwrap(),
+      Error::OffsetElementBounds {
+        buffer:   16,
+        elements: 16..17,
+      }
+    );
+  }
+}
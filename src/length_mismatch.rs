@@ -0,0 +1,24 @@
+use crate::common::*;
+
+/// Error passed to `Allocator::length_mismatch` when a slice or array
+/// serializer is given a different number of elements than it was created for.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct LengthMismatch {
+  /// Number of elements the serializer was created for.
+  pub expected: usize,
+  /// Number of elements actually serialized.
+  pub actual:   usize,
+}
+
+impl Display for LengthMismatch {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "Expected {} elements but {} were serialized",
+      self.expected, self.actual
+    )
+  }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LengthMismatch {}
@@ -7,22 +7,37 @@
 #![feature(raw_ref_op)]
 #![feature(try_reserve)]
 #![feature(assert_matches)]
+#![cfg_attr(all(feature = "std", feature = "rayon"), feature(specialization))]
 #![allow(incomplete_features)]
 #![allow(unused)] // TODO: Remove this
 
 // traits
 pub use crate::{
-  allocator::Allocator, continuation::Continuation, serializer::Serializer, view::View, x::X,
+  allocator::Allocator, continuation::Continuation, digest::Digest, from_view::FromView,
+  serializer::Serializer, stack::Stack, view::View, x::X,
 };
 
 // structs and enums
 pub use crate::{
+  array_stack::{ArrayStack, DEFAULT_DEPTH},
+  bit_slice::{BitSlice, BitSliceSerializer, Bits},
   char::{Char, CharSerializer},
+  depth_exceeded::DepthExceeded,
   done::Done,
   error::Error,
+  fallible_slice_allocator::FallibleSliceAllocator,
+  lazy_view::LazyView,
+  length_mismatch::LengthMismatch,
+  limit_error::LimitError,
+  limited::Limited,
+  load_options::LoadOptions,
+  overflow::Overflow,
+  segment::Segment,
+  size_allocator::SizeAllocator,
   slice_allocator::SliceAllocator,
   state::State,
   usize::{Usize, UsizeSerializer},
+  validator::Validator,
 };
 
 // signed inegers
@@ -36,10 +51,21 @@ pub use crate::integer::{
 };
 
 #[cfg(feature = "alloc")]
-pub use crate::{fallible_vec_allocator::FallibleVecAllocator, vec_allocator::VecAllocator};
+pub use crate::{
+  bit_vec::BitVec, chunked_allocator::ChunkedAllocator,
+  fallible_vec_allocator::FallibleVecAllocator, hashing_allocator::HashingAllocator,
+  message::Message, reserve_error::ReserveError, reuse_allocator::ReuseAllocator, rope::Rope,
+  storage::Storage, vec_allocator::VecAllocator, vec_stack::VecStack,
+};
 
 #[cfg(feature = "std")]
-pub use crate::file_allocator::FileAllocator;
+pub use crate::{file_allocator::FileAllocator, stream_allocator::StreamAllocator};
+
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+pub use crate::mmap_allocator::MmapAllocator;
+
+#[cfg(all(feature = "std", feature = "tokio"))]
+pub use crate::tokio_allocator::{TokioAllocator, TokioWrites};
 
 #[doc(hidden)]
 /// This export is used by `x-derive` to access `core`
@@ -64,27 +90,41 @@ extern crate std;
 
 mod allocator;
 mod array;
+mod array_stack;
+mod bit_slice;
 mod bool;
 mod char;
 mod common;
 mod continuation;
+mod depth_exceeded;
+mod digest;
 mod done;
 mod error;
+mod fallible_slice_allocator;
 mod from_view;
 mod i8;
 mod integer;
 mod is;
 mod isize;
+mod lazy_view;
+mod length_mismatch;
+mod limit_error;
+mod limited;
+mod load_options;
 mod maybe_uninit_ext;
 mod offset;
 mod option;
+mod overflow;
 mod padding_serializer;
 mod range_ext;
 mod result;
+mod segment;
 mod serialize;
 mod serializer;
+mod size_allocator;
 mod slice;
 mod slice_allocator;
+mod stack;
 mod state;
 mod str;
 mod to_i64;
@@ -92,15 +132,43 @@ mod to_u64;
 mod u8;
 mod unit;
 mod usize;
+mod validator;
 mod view;
 mod x;
 
 #[cfg(feature = "alloc")]
 mod alloc_impls;
 
+#[cfg(feature = "alloc")]
+mod bit_vec;
+
+#[cfg(feature = "alloc")]
+mod chunked_allocator;
+
+#[cfg(feature = "alloc")]
+mod hashing_allocator;
+
+#[cfg(feature = "alloc")]
+mod message;
+
+#[cfg(feature = "alloc")]
+mod reserve_error;
+
+#[cfg(feature = "alloc")]
+mod reuse_allocator;
+
+#[cfg(feature = "alloc")]
+mod rope;
+
+#[cfg(feature = "alloc")]
+mod storage;
+
 #[cfg(feature = "alloc")]
 mod vec_allocator;
 
+#[cfg(feature = "alloc")]
+mod vec_stack;
+
 #[cfg(feature = "alloc")]
 mod fallible_vec_allocator;
 
@@ -110,5 +178,17 @@ mod vec_ext;
 #[cfg(feature = "std")]
 mod file_allocator;
 
+#[cfg(feature = "std")]
+mod stream_allocator;
+
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+mod mmap_allocator;
+
+#[cfg(all(feature = "std", feature = "rayon"))]
+mod shared_totals;
+
+#[cfg(all(feature = "std", feature = "tokio"))]
+mod tokio_allocator;
+
 #[cfg(test)]
 mod test;
@@ -0,0 +1,11 @@
+/// Error returned by `Limited`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum LimitError<E> {
+  /// The message is longer than the limit.
+  Exceeded {
+    /// Maximum message length, in bytes.
+    limit: usize,
+  },
+  /// The wrapped allocator returned an error.
+  Allocator(E),
+}
@@ -0,0 +1,106 @@
+use crate::common::*;
+
+/// Wraps another allocator, failing with `LimitError::Exceeded` instead of
+/// writing past `limit` bytes, so that an unexpectedly large message can't
+/// exhaust memory or disk.
+pub struct Limited<A: Allocator> {
+  inner: A,
+  limit: usize,
+}
+
+impl<A: Allocator> Limited<A> {
+  pub fn new(inner: A, limit: usize) -> Limited<A> {
+    Self { inner, limit }
+  }
+}
+
+impl<A: Allocator> Allocator for Limited<A> {
+  type Error = LimitError<A::Error>;
+  type Output = A::Output;
+  type Stack = A::Stack;
+
+  fn write(&mut self, bytes: &[u8], offset: usize) -> Result<(), Self::Error> {
+    if offset + bytes.len() > self.limit {
+      return Err(LimitError::Exceeded { limit: self.limit });
+    }
+
+    self
+      .inner
+      .write(bytes, offset)
+      .map_err(LimitError::Allocator)
+  }
+
+  fn commit(&mut self, offset: usize) -> Result<(), Self::Error> {
+    self.inner.commit(offset).map_err(LimitError::Allocator)
+  }
+
+  fn finish(self, end: usize) -> Result<Self::Output, Self::Error> {
+    // Zero-length regions past the limit are never written, so the message may
+    // still exceed the limit:
+    if end > self.limit {
+      return Err(LimitError::Exceeded { limit: self.limit });
+    }
+
+    self.inner.finish(end).map_err(LimitError::Allocator)
+  }
+
+  fn stack(&mut self) -> Self::Stack {
+    self.inner.stack()
+  }
+
+  fn reclaim(&mut self, stack: Self::Stack) {
+    self.inner.reclaim(stack)
+  }
+
+  fn depth_exceeded(&mut self, error: DepthExceeded) -> Result<(), Self::Error> {
+    self
+      .inner
+      .depth_exceeded(error)
+      .map_err(LimitError::Allocator)
+  }
+
+  fn length_mismatch(&mut self, error: LengthMismatch) -> Self::Error {
+    LimitError::Allocator(self.inner.length_mismatch(error))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn within_limit() {
+    round_trip(|native| {
+      <Vec<String>>::store(Limited::new(
+        VecAllocator::new(),
+        native.serialized_size().unwrap(),
+      ))
+      .serialize(native)
+      .done()
+      .unwrap()
+    });
+  }
+
+  #[test]
+  fn exceeded() {
+    let have = <Vec<u8>>::store(Limited::new(VecAllocator::new(), 1024))
+      .serialize(&vec![0u8; 1 << 20])
+      .done();
+
+    assert_eq!(have, Err(LimitError::Exceeded { limit: 1024 }));
+  }
+
+  #[test]
+  fn inner_error() {
+    let mut buffer = [0; 8];
+
+    let have = <Vec<u8>>::store(Limited::new(FallibleSliceAllocator::new(&mut buffer), 1024))
+      .serialize(&vec![1u8, 2, 3])
+      .done();
+
+    assert_eq!(
+      have,
+      Err(LimitError::Allocator(Overflow::Buffer { needed: 19 }))
+    );
+  }
+}
@@ -0,0 +1,30 @@
+/// Limits on the work done to validate a message, for loading messages from
+/// untrusted sources with `View::load_with`.
+///
+/// Slices may overlap, so without limits, validating a message can take time
+/// far out of proportion to its size, and deeply nested messages can exhaust
+/// the stack. The default options impose no limits, and accept non-canonical
+/// messages.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct LoadOptions {
+  /// Maximum number of offsets followed from the root to any view.
+  pub max_depth:    usize,
+  /// Maximum total number of elements pointed to by all offsets.
+  pub max_elements: usize,
+  /// Maximum total number of bytes pointed to by all offsets.
+  pub max_bytes:    usize,
+  /// Reject messages which are not the canonical encoding of their value. See
+  /// `View::load_canonical`.
+  pub canonical:    bool,
+}
+
+impl Default for LoadOptions {
+  fn default() -> Self {
+    Self {
+      max_depth:    usize::MAX,
+      max_elements: usize::MAX,
+      max_bytes:    usize::MAX,
+      canonical:    false,
+    }
+  }
+}
@@ -2,6 +2,10 @@ use crate::common::*;
 
 pub(crate) trait MaybeUninitExt<T> {
   fn cast<U>(&self) -> &MaybeUninit<U>;
+
+  fn check(&self, validator: &mut Validator) -> Result<&T>
+  where
+    T: View;
 }
 
 impl<T> MaybeUninitExt<T> for MaybeUninit<T> {
@@ -18,4 +22,19 @@ impl<T> MaybeUninitExt<T> for MaybeUninit<T> {
     // - MaybeUinint<U> is the same size or smaller than MaybeUninit<T>
     unsafe { &*pointer }
   }
+
+  /// Check that `self` is a valid `T`, and if so, return a reference to it.
+  ///
+  /// If `validator` is shallow, only the in-line part of the value has been
+  /// checked, and the reference must not be exposed. See `View`.
+  fn check(&self, validator: &mut Validator) -> Result<&T>
+  where
+    T: View,
+  {
+    T::check(self, validator)?;
+
+    // Safe because implementations of `View` guarantee that `check` only
+    // succeeds if `self` is valid.
+    Ok(unsafe { self.assume_init_ref() })
+  }
 }
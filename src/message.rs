@@ -0,0 +1,152 @@
+use crate::common::*;
+
+/// A validated message which owns its bytes.
+///
+/// Unlike a view returned by `View::load`, which borrows from its buffer, a
+/// message can be stored, returned, and sent between threads along with the
+/// bytes it was loaded from. The bytes are validated once, when the message is
+/// created, and the message derefs to `T::View` without further checks.
+pub struct Message<T: X, S: Storage = Vec<u8>> {
+  storage: S,
+  view:    PhantomData<T::View>,
+}
+
+impl<T: X, S: Storage> Message<T, S> {
+  pub fn load(storage: S) -> Result<Self> {
+    Self::load_with(storage, &LoadOptions::default())
+  }
+
+  pub fn load_with(storage: S, options: &LoadOptions) -> Result<Self> {
+    T::View::load_with(storage.bytes(), options)?;
+
+    Ok(Self {
+      view: PhantomData,
+      storage,
+    })
+  }
+
+  pub fn view(&self) -> &T::View {
+    // Safe because the bytes were validated when the message was created, and
+    // `Storage` guarantees that they have not changed since.
+    unsafe { &*(self.storage.bytes().as_ptr() as *const T::View) }
+  }
+
+  pub fn bytes(&self) -> &[u8] {
+    self.storage.bytes()
+  }
+
+  pub fn into_bytes(self) -> S {
+    self.storage
+  }
+
+  /// Move the message into an `Arc`, so that it can be cheaply cloned.
+  pub fn into_shared(self) -> Message<T, Arc<[u8]>> {
+    // `Storage::into_shared` returns the same bytes, so they are still valid:
+    Message {
+      storage: self.storage.into_shared(),
+      view:    PhantomData,
+    }
+  }
+}
+
+impl<T: X> Message<T> {
+  /// Serialize `value` into a new message.
+  ///
+  /// The serialized bytes are validated like those of any other message,
+  /// since `X` implementations are not trusted to produce valid views.
+  pub fn new(value: &T) -> Result<Self> {
+    Self::load(value.serialize_to_vec()?)
+  }
+}
+
+impl<T: X, S: Storage> Deref for Message<T, S> {
+  type Target = T::View;
+
+  fn deref(&self) -> &T::View {
+    self.view()
+  }
+}
+
+impl<T: X, S: Storage + Clone> Clone for Message<T, S> {
+  fn clone(&self) -> Self {
+    Self {
+      storage: self.storage.clone(),
+      view:    PhantomData,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn load() {
+    let message =
+      Message::<String>::load(String::from("hello").serialize_to_vec().unwrap()).unwrap();
+    assert_eq!(message.as_str(), "hello");
+
+    let message =
+      Message::<String, Box<[u8]>>::load(String::from("hello").serialize_to_vec().unwrap().into())
+        .unwrap();
+    assert_eq!(message.as_str(), "hello");
+  }
+
+  #[test]
+  fn load_error() {
+    assert_eq!(Message::<bool>::load(vec![2]).err().unwrap(), Error::Bool {
+      value: 2,
+    });
+  }
+
+  #[test]
+  fn new() {
+    let message = Message::new(&vec![1u8, 2, 3]).unwrap();
+    assert_eq!(message.as_slice(), &[1, 2, 3]);
+    assert_eq!(
+      message.into_bytes(),
+      vec![1u8, 2, 3].serialize_to_vec().unwrap()
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "std")]
+  fn shared() {
+    let message = Message::new(&String::from("hello")).unwrap().into_shared();
+
+    let clone = message.clone();
+
+    let thread = std::thread::spawn(move || clone.as_str().len());
+
+    assert_eq!(thread.join().unwrap(), 5);
+    assert_eq!(message.as_str(), "hello");
+  }
+
+  #[test]
+  fn into_shared() {
+    let bytes = String::from("hello").serialize_to_vec().unwrap();
+
+    let message = Message::<String, &[u8]>::load(&bytes)
+      .unwrap()
+      .into_shared();
+    assert_eq!(message.as_str(), "hello");
+
+    let message = Message::<String>::load(bytes).unwrap().into_shared();
+    assert_eq!(message.as_str(), "hello");
+  }
+
+  #[test]
+  fn owned() {
+    fn message() -> Message<String> {
+      Message::load(String::from("hello").serialize_to_vec().unwrap()).unwrap()
+    }
+
+    struct Holder {
+      message: Message<String>,
+    }
+
+    let holder = Holder { message: message() };
+
+    assert_eq!(holder.message.as_str(), "hello");
+  }
+}
@@ -0,0 +1,136 @@
+use crate::common::*;
+
+/// Minimum number of bytes by which the file is grown when a write doesn't fit.
+const GROWTH: usize = 1 << 20;
+
+/// Serializes into a memory-mapped file.
+///
+/// Bytes are written directly into the mapping, and the file is grown in large
+/// steps, so serializing a large message does not require a syscall per write.
+/// On completion, the file is truncated to the length of the message, and a
+/// read-only mapping of it is returned, ready to be passed to `View::load`.
+pub struct MmapAllocator {
+  file:     File,
+  mmap:     Option<MmapMut>,
+  capacity: usize,
+}
+
+impl MmapAllocator {
+  /// Create a new allocator that serializes into `file`, which must be open for
+  /// reading and writing. Any existing contents of `file` are overwritten.
+  ///
+  /// # Safety
+  ///
+  /// The file must not be modified, truncated, or mapped by anything else while
+  /// the allocator or the mapping it returns are alive, since the contents of
+  /// the mapping would change out from under references to it.
+  pub unsafe fn new(file: File) -> MmapAllocator {
+    Self {
+      mmap: None,
+      capacity: 0,
+      file,
+    }
+  }
+
+  /// Grow the file and mapping so that they are at least `end` bytes long.
+  fn grow(&mut self, end: usize) -> io::Result<()> {
+    let capacity = end.max(self.capacity * 2).max(GROWTH);
+
+    // Unmap before resizing, so the file is never smaller than the mapping:
+    self.mmap = None;
+
+    self.file.set_len(capacity.to_u64())?;
+
+    // Safe because the caller of `new` guaranteed that the file will not be
+    // modified by anything else while it is mapped.
+    self.mmap = Some(unsafe { MmapMut::map_mut(&self.file)? });
+    self.capacity = capacity;
+
+    Ok(())
+  }
+}
+
+impl Allocator for MmapAllocator {
+  type Error = io::Error;
+  type Output = Mmap;
+  type Stack = VecStack;
+
+  fn write(&mut self, bytes: &[u8], offset: usize) -> Result<(), Self::Error> {
+    let end = offset + bytes.len();
+
+    if end > self.capacity {
+      self.grow(end)?;
+    }
+
+    if let Some(mmap) = &mut self.mmap {
+      mmap[offset..end].copy_from_slice(bytes);
+    }
+
+    Ok(())
+  }
+
+  fn finish(mut self, end: usize) -> Result<Self::Output, Self::Error> {
+    assert!(end <= self.capacity);
+
+    self.mmap = None;
+
+    self.file.set_len(end.to_u64())?;
+
+    // Safe because the caller of `new` guaranteed that the file will not be
+    // modified by anything else while it is mapped.
+    unsafe { Mmap::map(&self.file) }
+  }
+
+  fn length_mismatch(&mut self, error: LengthMismatch) -> Self::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, error)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use std::{format, fs::OpenOptions, process};
+
+  fn store<N: X>(name: &str, native: N) -> Mmap {
+    let path =
+      std::env::temp_dir().join(format!("x-mmap-allocator-test-{}-{}", process::id(), name));
+
+    let file = OpenOptions::new()
+      .read(true)
+      .write(true)
+      .create_new(true)
+      .open(&path)
+      .unwrap();
+
+    let mmap = N::store(unsafe { MmapAllocator::new(file) })
+      .serialize(&native)
+      .done()
+      .unwrap();
+
+    std::fs::remove_file(&path).unwrap();
+
+    mmap
+  }
+
+  #[test]
+  fn empty() {
+    assert_eq!(&store("empty", ())[..], &[]);
+  }
+
+  #[test]
+  fn basic() {
+    round_trip(|native| store("basic", native));
+  }
+
+  #[test]
+  fn grow() {
+    let native = (0..GROWTH / 2).map(|i| i as u32).collect::<Vec<u32>>();
+
+    let mmap = store("grow", native.clone());
+
+    assert_eq!(mmap.len(), 16 + GROWTH * 2);
+
+    assert_eq!(&mmap[..], native.serialize_to_vec().unwrap().as_slice());
+  }
+}
@@ -20,24 +20,29 @@ impl<V: View> Offset<V> {
   /// elements.
   pub(crate) fn check<'value>(
     suspect: &'value MaybeUninit<Self>,
-    buffer: &[u8],
+    validator: &mut Validator,
     length: usize,
   ) -> Result<&'value [V]> {
-    let buffer_range = Range {
-      start: buffer.as_ptr(),
-      end:   buffer.as_ptr().wrapping_add(buffer.len()),
-    };
+    assert_eq!(
+      mem::align_of::<V>(),
+      1,
+      "View types must have alignment 1. Alignment is {}.",
+      mem::align_of::<V>(),
+    );
+
+    let buffer = validator.buffer();
+
+    // Position of the offset relative to the start of the buffer, which is
+    // negative if the offset precedes the buffer:
+    let position = (suspect.as_ptr() as usize).wrapping_sub(buffer.as_ptr() as usize) as isize;
 
     // Check that offset itself is contained within the buffer:
     {
-      let offset = Range {
-        start: suspect.as_ptr() as *const u8,
-        end:   (suspect.as_ptr() as *const u8).wrapping_add(mem::size_of::<Self>()),
-      };
+      let offset = position..position.wrapping_add(mem::size_of::<Self>() as isize);
 
-      if !buffer_range.contains_range(&offset) {
+      if !(0..buffer.len() as isize).contains_range(&offset) {
         return Err(Error::OffsetBounds {
-          buffer: buffer_range,
+          buffer: buffer.len(),
           offset,
         });
       }
@@ -45,7 +50,7 @@ impl<V: View> Offset<V> {
 
     let inner = suspect.cast::<Usize>();
 
-    let inner = View::check(inner, buffer)?;
+    let inner = inner.check(validator)?;
 
     let offset = inner.to_native();
 
@@ -57,42 +62,55 @@ impl<V: View> Offset<V> {
       return Err(Error::OffsetValue { value: offset });
     }
 
-    let value = unsafe { suspect.assume_init_ref() };
-
-    let start = value.as_ptr();
-
-    // Check that elements are within range:
-    {
-      let start = start as *const u8;
-
-      let end = start.wrapping_add(length * mem::size_of::<V>());
+    // Check that elements are within range. No buffer is longer than
+    // `isize::MAX` bytes, so elements that end past it overflow:
+    let elements = offset
+      .try_into()
+      .ok()
+      .and_then(|offset| position.checked_add(offset))
+      .and_then(|start| {
+        let bytes = length.checked_mul(mem::size_of::<V>())?;
+        let end = start.checked_add(bytes.try_into().ok()?)?;
+        Some(start..end)
+      });
+
+    let elements = match elements {
+      Some(elements) => elements,
+      None =>
+        return Err(Error::OffsetWrap {
+          offset: position as usize,
+          value: offset,
+          length,
+        }),
+    };
 
-      if end < start {
-        return Err(Error::OffsetWrap { start, end });
-      }
+    if elements.end > buffer.len() as isize {
+      return Err(Error::OffsetElementBounds {
+        buffer: buffer.len(),
+        elements,
+      });
+    }
 
-      let element_range = Range { start, end };
+    let elements = elements.start as usize..elements.end as usize;
 
-      if !buffer_range.contains_range(&element_range) {
-        return Err(Error::OffsetElementBounds {
-          buffer:   buffer_range,
-          elements: element_range,
-        });
-      }
-    }
+    // Safe because the elements were checked to be within the buffer.
+    let start = unsafe { buffer.as_ptr().add(elements.start) };
 
     let slice: &[MaybeUninit<V>] =
       unsafe { slice::from_raw_parts(start as *const MaybeUninit<V>, length) };
 
-    for element in slice {
-      View::check(element, buffer)?;
+    validator.allocate(elements.clone())?;
+
+    validator.enter(length, elements.len())?;
+
+    if !validator.is_shallow() {
+      validator.check_elements(slice)?;
     }
 
-    Ok(value.to_slice(length))
-  }
+    validator.exit();
 
-  fn to_slice(&self, length: usize) -> &[V] {
-    unsafe { slice::from_raw_parts(self.as_ptr(), length) }
+    // All elements are valid, so the slice is valid.
+    Ok(unsafe { slice::from_raw_parts(start as *const V, length) })
   }
 }
 
@@ -100,6 +118,10 @@ impl<V: View> Offset<V> {
 mod tests {
   use super::*;
 
+  fn validator(buffer: &[u8]) -> Validator<'_> {
+    Validator::new(buffer, &LoadOptions::default())
+  }
+
   #[test]
   fn error_not_in_buffer() {
     let buffer: &[u8] = &[8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
@@ -107,16 +129,10 @@ mod tests {
     let offset = unsafe { &*(buffer.as_ptr() as *const MaybeUninit<Offset<u8>>) };
 
     assert_eq!(
-      Offset::check(offset, &buffer[8..16], 0).unwrap_err(),
+      Offset::check(offset, &mut validator(&buffer[8..16]), 0).unwrap_err(),
       Error::OffsetBounds {
-        offset: Range {
-          start: buffer.as_ptr(),
-          end:   buffer.as_ptr().wrapping_add(8),
-        },
-        buffer: Range {
-          start: buffer.as_ptr().wrapping_add(8),
-          end:   buffer.as_ptr().wrapping_add(16),
-        },
+        offset: -8..0,
+        buffer: 8,
       },
     );
   }
@@ -128,7 +144,7 @@ mod tests {
     let offset = unsafe { &*(buffer.as_ptr() as *const MaybeUninit<Offset<u8>>) };
 
     assert_eq!(
-      Offset::check(offset, buffer, 0).unwrap_err(),
+      Offset::check(offset, &mut validator(buffer), 0).unwrap_err(),
       Error::OffsetNull,
     );
   }
@@ -140,7 +156,7 @@ mod tests {
     let offset = unsafe { &*(buffer.as_ptr() as *const MaybeUninit<Offset<u8>>) };
 
     assert_eq!(
-      Offset::check(offset, buffer, 0).unwrap_err(),
+      Offset::check(offset, &mut validator(buffer), 0).unwrap_err(),
       Error::OffsetValue { value: 7 }
     );
   }
@@ -152,10 +168,51 @@ mod tests {
     let offset = unsafe { &*(buffer.as_ptr() as *const MaybeUninit<Offset<u8>>) };
 
     assert_eq!(
-      Offset::check(offset, buffer, usize::MAX - 8).unwrap_err(),
+      Offset::check(offset, &mut validator(buffer), usize::MAX - 8).unwrap_err(),
+      Error::OffsetWrap {
+        offset: 0,
+        value:  8,
+        length: usize::MAX - 8,
+      }
+    );
+  }
+
+  #[test]
+  fn error_wrap_end() {
+    let buffer: &[u8] = &[8, 0, 0, 0, 0, 0, 0, 0];
+
+    let offset = unsafe { &*(buffer.as_ptr() as *const MaybeUninit<Offset<u8>>) };
+
+    assert_eq!(
+      Offset::check(offset, &mut validator(buffer), isize::MAX as usize - 7).unwrap_err(),
+      Error::OffsetWrap {
+        offset: 0,
+        value:  8,
+        length: isize::MAX as usize - 7,
+      }
+    );
+
+    assert_eq!(
+      Offset::check(offset, &mut validator(buffer), isize::MAX as usize - 8).unwrap_err(),
+      Error::OffsetElementBounds {
+        buffer:   8,
+        elements: 8..isize::MAX,
+      }
+    );
+  }
+
+  #[test]
+  fn error_wrap_length() {
+    let buffer: &[u8] = &[8, 0, 0, 0, 0, 0, 0, 0];
+
+    let offset = unsafe { &*(buffer.as_ptr() as *const MaybeUninit<Offset<U64>>) };
+
+    assert_eq!(
+      Offset::check(offset, &mut validator(buffer), usize::MAX / 8 + 1).unwrap_err(),
       Error::OffsetWrap {
-        start: buffer.as_ptr().wrapping_add(8),
-        end:   buffer.as_ptr().wrapping_add(usize::MAX),
+        offset: 0,
+        value:  8,
+        length: usize::MAX / 8 + 1,
       }
     );
   }
@@ -167,16 +224,10 @@ mod tests {
     let offset = unsafe { &*(buffer.as_ptr() as *const MaybeUninit<Offset<u8>>) };
 
     assert_eq!(
-      Offset::check(offset, buffer, 0).unwrap_err(),
+      Offset::check(offset, &mut validator(buffer), 0).unwrap_err(),
       Error::OffsetElementBounds {
-        buffer:   Range {
-          start: buffer.as_ptr(),
-          end:   buffer.as_ptr().wrapping_add(buffer.len()),
-        },
-        elements: Range {
-          start: buffer.as_ptr().wrapping_add(9),
-          end:   buffer.as_ptr().wrapping_add(9),
-        },
+        buffer:   8,
+        elements: 9..9,
       }
     );
   }
@@ -188,16 +239,10 @@ mod tests {
     let offset = unsafe { &*(buffer.as_ptr() as *const MaybeUninit<Offset<u8>>) };
 
     assert_eq!(
-      Offset::check(offset, buffer, 1).unwrap_err(),
+      Offset::check(offset, &mut validator(buffer), 1).unwrap_err(),
       Error::OffsetElementBounds {
-        buffer:   Range {
-          start: buffer.as_ptr(),
-          end:   buffer.as_ptr().wrapping_add(buffer.len()),
-        },
-        elements: Range {
-          start: buffer.as_ptr().wrapping_add(8),
-          end:   buffer.as_ptr().wrapping_add(9),
-        },
+        buffer:   8,
+        elements: 8..9,
       }
     );
   }
@@ -208,9 +253,10 @@ mod tests {
 
     let offset = unsafe { &*(buffer.as_ptr() as *const MaybeUninit<Offset<bool>>) };
 
-    assert_eq!(Offset::check(offset, buffer, 3).unwrap_err(), Error::Bool {
-      value: 2,
-    },);
+    assert_eq!(
+      Offset::check(offset, &mut validator(buffer), 3).unwrap_err(),
+      Error::Bool { value: 2 }.at(Segment::Index(0))
+    );
   }
 
   #[test]
@@ -219,9 +265,10 @@ mod tests {
 
     let offset = unsafe { &*(buffer.as_ptr() as *const MaybeUninit<Offset<bool>>) };
 
-    assert_eq!(Offset::check(offset, buffer, 3).unwrap_err(), Error::Bool {
-      value: 2,
-    },);
+    assert_eq!(
+      Offset::check(offset, &mut validator(buffer), 3).unwrap_err(),
+      Error::Bool { value: 2 }.at(Segment::Index(1))
+    );
   }
 
   #[test]
@@ -230,9 +277,10 @@ mod tests {
 
     let offset = unsafe { &*(buffer.as_ptr() as *const MaybeUninit<Offset<bool>>) };
 
-    assert_eq!(Offset::check(offset, buffer, 3).unwrap_err(), Error::Bool {
-      value: 2,
-    },);
+    assert_eq!(
+      Offset::check(offset, &mut validator(buffer), 3).unwrap_err(),
+      Error::Bool { value: 2 }.at(Segment::Index(2))
+    );
   }
 
   #[test]
@@ -241,6 +289,6 @@ mod tests {
 
     let offset = unsafe { &*(buffer.as_ptr() as *const MaybeUninit<Offset<u8>>) };
 
-    Offset::check(offset, buffer, 0).unwrap();
+    Offset::check(offset, &mut validator(buffer), 0).unwrap();
   }
 }
@@ -43,20 +43,23 @@ pub enum Option<V: View> {
   Some(V) = SOME_DISCRIMINANT,
 }
 
-impl<V: View> View for self::Option<V> {
+unsafe impl<V: View> View for self::Option<V> {
   type Serializer<A: Allocator, C: Continuation<A>> = OptionSerializer<A, C, V>;
 
-  fn check<'value>(suspect: &'value MaybeUninit<Self>, buffer: &[u8]) -> Result<&'value Self> {
+  fn check(suspect: &MaybeUninit<Self>, validator: &mut Validator) -> Result<()> {
     let pointer = suspect.as_ptr() as *const u8;
 
     let discriminant = unsafe { *pointer };
 
     match discriminant {
-      NONE_DISCRIMINANT => Ok(unsafe { suspect.assume_init_ref() }),
+      NONE_DISCRIMINANT => {
+        // None is serialized as all zeros:
+        let padding = unsafe { slice::from_raw_parts(pointer.add(1), mem::size_of::<Self>() - 1) };
+        validator.padding(padding)
+      },
       SOME_DISCRIMINANT => {
         let payload = unsafe { pointer.add(1) } as *const MaybeUninit<V>;
-        View::check(unsafe { &*payload }, buffer)?;
-        Ok(unsafe { suspect.assume_init_ref() })
+        View::check(unsafe { &*payload }, validator)
       },
       value => Err(Error::Discriminant {
         maximum: SOME_DISCRIMINANT,
@@ -106,7 +109,7 @@ mod tests {
     let view = Option::<u8>::load(buffer).unwrap();
     assert_matches!(view, Option::None);
   }
-  
+
   #[test]
   fn match_some() {
     let buffer = &[1, 77];
@@ -114,7 +117,6 @@ mod tests {
     assert_matches!(view, Option::Some(77));
   }
 
-
   #[test]
   fn invalid_discriminant() {
     assert_eq!(
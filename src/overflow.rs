@@ -0,0 +1,19 @@
+use crate::common::*;
+
+/// Error returned when a message does not fit in the buffer it is being
+/// serialized into, or cannot be serialized at all.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Overflow {
+  /// The message is longer than the buffer.
+  Buffer {
+    /// Number of bytes required to store the message.
+    needed: usize,
+  },
+  /// The message is nested more deeply than the allocator's stack can hold.
+  Stack {
+    /// Capacity of the stack.
+    depth: usize,
+  },
+  /// A slice or array serializer was given the wrong number of elements.
+  Mismatch(LengthMismatch),
+}
@@ -0,0 +1,10 @@
+use crate::common::*;
+
+/// Error returned by `FallibleVecAllocator`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ReserveError {
+  /// Space for the message could not be reserved.
+  Capacity(TryReserveError),
+  /// A slice or array serializer was given the wrong number of elements.
+  Mismatch(LengthMismatch),
+}
@@ -24,13 +24,10 @@ pub enum Result<T: View, E: View> {
   Err(E) = ERR_DISCRIMINANT,
 }
 
-impl<T: View, E: View> View for self::Result<T, E> {
+unsafe impl<T: View, E: View> View for self::Result<T, E> {
   type Serializer<A: Allocator, C: Continuation<A>> = ResultSerializer<A, C, T, E>;
 
-  fn check<'value>(
-    suspect: &'value MaybeUninit<Self>,
-    buffer: &[u8],
-  ) -> crate::Result<&'value Self> {
+  fn check(suspect: &MaybeUninit<Self>, validator: &mut Validator) -> crate::Result<()> {
     let pointer = suspect.as_ptr() as *const u8;
     let payload = unsafe { pointer.add(1) };
 
@@ -39,13 +36,13 @@ impl<T: View, E: View> View for self::Result<T, E> {
     match discriminant {
       OK_DISCRIMINANT => {
         let payload = payload as *const MaybeUninit<T>;
-        View::check(unsafe { &*payload }, buffer)?;
-        Ok(unsafe { suspect.assume_init_ref() })
+        View::check(unsafe { &*payload }, validator)?;
+        validator.padding(unsafe { padding::<Self, T>(pointer) })
       },
       ERR_DISCRIMINANT => {
         let payload = payload as *const MaybeUninit<E>;
-        View::check(unsafe { &*payload }, buffer)?;
-        Ok(unsafe { suspect.assume_init_ref() })
+        View::check(unsafe { &*payload }, validator)?;
+        validator.padding(unsafe { padding::<Self, E>(pointer) })
       },
       value => Err(Error::Discriminant {
         maximum: ERR_DISCRIMINANT,
@@ -56,6 +53,14 @@ impl<T: View, E: View> View for self::Result<T, E> {
   }
 }
 
+/// The padding following a payload of type `P` in the result at `pointer`.
+///
+/// Safety: `pointer` must point to a `Result` of type `R` with payload `P`.
+unsafe fn padding<'a, R: View, P: View>(pointer: *const u8) -> &'a [u8] {
+  let start = 1 + mem::size_of::<P>();
+  slice::from_raw_parts(pointer.add(start), mem::size_of::<R>() - start)
+}
+
 pub struct ResultSerializer<A: Allocator, C: Continuation<A>, T: View, E: View> {
   state: State<A, C>,
   t:     PhantomData<T>,
@@ -0,0 +1,76 @@
+use crate::common::*;
+
+/// Serializes into a borrowed `Vec<u8>`, reusing its capacity, and a borrowed
+/// `VecStack`, so that serializing messages in a loop does not allocate once
+/// the buffers have grown large enough.
+pub struct ReuseAllocator<'buffer> {
+  vec:   &'buffer mut Vec<u8>,
+  stack: &'buffer mut VecStack,
+}
+
+impl<'buffer> ReuseAllocator<'buffer> {
+  /// Create a new allocator. `vec` is cleared, but its capacity is retained.
+  pub fn new(vec: &'buffer mut Vec<u8>, stack: &'buffer mut VecStack) -> ReuseAllocator<'buffer> {
+    vec.clear();
+    Self { vec, stack }
+  }
+}
+
+impl<'buffer> Allocator for ReuseAllocator<'buffer> {
+  type Error = LengthMismatch;
+  type Output = &'buffer [u8];
+  type Stack = VecStack;
+
+  fn write(&mut self, bytes: &[u8], offset: usize) -> Result<(), Self::Error> {
+    self.vec.place(bytes, offset);
+    Ok(())
+  }
+
+  fn finish(self, end: usize) -> Result<Self::Output, Self::Error> {
+    assert_eq!(self.vec.len(), end);
+    Ok(self.vec)
+  }
+
+  fn stack(&mut self) -> VecStack {
+    mem::take(self.stack)
+  }
+
+  fn reclaim(&mut self, stack: VecStack) {
+    *self.stack = stack;
+  }
+
+  fn length_mismatch(&mut self, error: LengthMismatch) -> Self::Error {
+    error
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn reuse() {
+    let mut vec = Vec::new();
+    let mut stack = VecStack::new();
+
+    round_trip(|native| {
+      <Vec<String>>::store(ReuseAllocator::new(&mut vec, &mut stack))
+        .serialize(native)
+        .done()
+        .unwrap()
+    });
+
+    let pointer = vec.as_ptr();
+
+    let second = vec![String::from("baz")];
+
+    let have = <Vec<String>>::store(ReuseAllocator::new(&mut vec, &mut stack))
+      .serialize(&second)
+      .done()
+      .unwrap();
+
+    assert_eq!(have, second.serialize_to_vec().unwrap().as_slice());
+
+    assert_eq!(have.as_ptr(), pointer);
+  }
+}
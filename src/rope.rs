@@ -0,0 +1,52 @@
+use crate::common::*;
+
+/// A message stored as a sequence of chunks, produced by `ChunkedAllocator`.
+pub struct Rope {
+  chunks: Vec<Box<[u8]>>,
+  len:    usize,
+}
+
+impl Rope {
+  pub(crate) fn new(chunks: Vec<Box<[u8]>>, len: usize) -> Rope {
+    Rope { chunks, len }
+  }
+
+  /// Total length of the message, in bytes.
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// Iterate over the chunks of the message, in order. All chunks but the last
+  /// are full, and the last is trimmed to the end of the message.
+  pub fn chunks(&self) -> impl Iterator<Item = &[u8]> {
+    let mut remaining = self.len;
+
+    self.chunks.iter().map(move |chunk| {
+      let len = chunk.len().min(remaining);
+      remaining -= len;
+      &chunk[..len]
+    })
+  }
+
+  /// The chunks of the message, suitable for passing to
+  /// `Write::write_vectored`.
+  #[cfg(feature = "std")]
+  pub fn io_slices(&self) -> Vec<IoSlice> {
+    self.chunks().map(IoSlice::new).collect()
+  }
+
+  /// Copy the message into a single contiguous buffer.
+  pub fn into_contiguous(self) -> Vec<u8> {
+    let mut contiguous = Vec::with_capacity(self.len);
+
+    for chunk in self.chunks() {
+      contiguous.extend_from_slice(chunk);
+    }
+
+    contiguous
+  }
+}
@@ -0,0 +1,20 @@
+use crate::common::*;
+
+/// One step in the path from the root of a message to the view in which a
+/// validation error occurred.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Segment {
+  /// A named or positional field of a struct
+  Field(&'static str),
+  /// An element of a slice or array
+  Index(usize),
+}
+
+impl Display for Segment {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Segment::Field(name) => write!(f, ".{}", name),
+      Segment::Index(index) => write!(f, "[{}]", index),
+    }
+  }
+}
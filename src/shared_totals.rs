@@ -0,0 +1,52 @@
+use crate::common::*;
+
+/// Totals of the work done by validators checking chunks of a slice in
+/// parallel, shared so that limits apply to all of them together, and each
+/// stops as soon as a limit is exceeded.
+pub(crate) struct SharedTotals {
+  elements: AtomicUsize,
+  bytes:    AtomicUsize,
+}
+
+impl SharedTotals {
+  pub(crate) fn new(elements: usize, bytes: usize) -> Self {
+    Self {
+      elements: AtomicUsize::new(elements),
+      bytes:    AtomicUsize::new(bytes),
+    }
+  }
+
+  pub(crate) fn elements(&self) -> usize {
+    self.elements.load(Ordering::Relaxed)
+  }
+
+  pub(crate) fn bytes(&self) -> usize {
+    self.bytes.load(Ordering::Relaxed)
+  }
+
+  /// Add `elements` and `bytes` to the totals, failing if either exceeds its
+  /// limit in `options`.
+  pub(crate) fn count(&self, elements: usize, bytes: usize, options: &LoadOptions) -> Result<()> {
+    if !Self::add(&self.elements, elements, options.max_elements) {
+      return Err(Error::ElementLimit {
+        maximum: options.max_elements,
+      });
+    }
+
+    if !Self::add(&self.bytes, bytes, options.max_bytes) {
+      return Err(Error::ByteLimit {
+        maximum: options.max_bytes,
+      });
+    }
+
+    Ok(())
+  }
+
+  fn add(total: &AtomicUsize, amount: usize, maximum: usize) -> bool {
+    total
+      .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |total| {
+        total.checked_add(amount).filter(|&total| total <= maximum)
+      })
+      .is_ok()
+  }
+}
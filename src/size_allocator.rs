@@ -0,0 +1,65 @@
+use crate::common::*;
+
+/// Computes the size of a message without storing it.
+///
+/// Useful for sizing a buffer exactly before serializing into it.
+#[derive(Default)]
+pub struct SizeAllocator;
+
+impl SizeAllocator {
+  pub fn new() -> SizeAllocator {
+    SizeAllocator
+  }
+}
+
+impl Allocator for SizeAllocator {
+  type Error = LengthMismatch;
+  type Output = usize;
+  type Stack = ArrayStack<DEFAULT_DEPTH>;
+
+  fn write(&mut self, _bytes: &[u8], _offset: usize) -> Result<(), Self::Error> {
+    Ok(())
+  }
+
+  fn finish(self, end: usize) -> Result<Self::Output, Self::Error> {
+    Ok(end)
+  }
+
+  /// Nothing is written, so skipped writes don't affect the size.
+  fn depth_exceeded(&mut self, _error: DepthExceeded) -> Result<(), Self::Error> {
+    Ok(())
+  }
+
+  fn length_mismatch(&mut self, error: LengthMismatch) -> Self::Error {
+    error
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn case<N: X>(native: N) {
+    assert_eq!(
+      native.serialized_size().unwrap(),
+      native.serialize_to_vec().unwrap().len()
+    );
+  }
+
+  #[test]
+  fn serialized_size() {
+    case(());
+    case(1u8);
+    case(1u64);
+    case('a');
+    case(Some(1u32));
+    case(core::option::Option::<u32>::None);
+    case("hello");
+    case(vec![1u16, 2, 3]);
+    case(vec![String::from("a"), String::from("bc")]);
+    case([1u8, 2]);
+    case(vec![vec![vec![vec![vec![vec![vec![vec![vec![vec![
+      vec![vec![vec![vec![vec![vec![vec![1u8]]]]]]],
+    ]]]]]]]]]]);
+  }
+}
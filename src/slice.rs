@@ -18,9 +18,20 @@ pub struct Slice<V: View> {
 }
 
 impl<V: View> Slice<V> {
+  pub fn len(&self) -> usize {
+    self.length.to_native()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
   pub fn as_slice(&self) -> &[V] {
-    let pointer = self.offset.as_ptr();
-    unsafe { slice::from_raw_parts(pointer, self.length.to_native()) }
+    unsafe { slice::from_raw_parts(self.as_ptr(), self.len()) }
+  }
+
+  pub(crate) fn as_ptr(&self) -> *const V {
+    self.offset.as_ptr()
   }
 }
 
@@ -33,20 +44,20 @@ impl<'a, V: View> IntoIterator for &'a Slice<V> {
   }
 }
 
-impl<V: View> View for Slice<V> {
+unsafe impl<V: View> View for Slice<V> {
   type Serializer<A: Allocator, C: Continuation<A>> = SliceSerializer<A, C, V>;
 
-  fn check<'value>(suspect: &'value MaybeUninit<Self>, buffer: &[u8]) -> Result<&'value Self> {
+  fn check(suspect: &MaybeUninit<Self>, validator: &mut Validator) -> Result<()> {
     let length: &MaybeUninit<Usize> =
       unsafe { &*((suspect.as_ptr() as *const Offset<V>).add(1) as *const MaybeUninit<Usize>) };
 
-    let length = View::check(length, buffer)?;
+    let length = length.check(validator)?;
 
     let offset = suspect.cast::<Offset<V>>();
 
-    Offset::check(offset, buffer, length.to_native())?;
+    Offset::check(offset, validator, length.to_native())?;
 
-    Ok(unsafe { suspect.assume_init_ref() })
+    Ok(())
   }
 }
 
@@ -77,11 +88,17 @@ impl<A: Allocator, C: Continuation<A>, V: View> SliceSerializer<A, C, V> {
   }
 
   fn len(mut self, length: usize) -> AllocatedSliceSerializer<A, C, V> {
-    let offset = self.state.end();
+    // Offsets are relative to their own location:
+    let offset = self.state.end() - self.state.position();
     self.state.write(&offset.to_u64().to_le_bytes());
     self.state.write(&length.to_u64().to_le_bytes());
-    let bytes = mem::size_of::<V>() * length;
-    self.state.push(bytes);
+
+    // Elements which don't fit in the address space are discarded. No iterator
+    // can yield that many, so the length is wrong, and is reported by `end`:
+    match mem::size_of::<V>().checked_mul(length) {
+      Some(bytes) => self.state.push(bytes),
+      None => self.state.discard(),
+    }
 
     AllocatedSliceSerializer {
       serialized: 0,
@@ -107,14 +124,18 @@ impl<A: Allocator, C: Continuation<A>, V: View> AllocatedSliceSerializer<A, C, V
   fn element_serializer<N: X<View = V>>(
     self,
   ) -> <N::View as View>::Serializer<A, AllocatedSliceSerializer<A, C, V>> {
-    if self.length == self.serialized {
-      todo!()
+    let mut state = self.state;
+
+    // Elements past the end of the slice have no space allocated for them, so
+    // they are discarded, and reported in `end`.
+    if self.serialized >= self.length {
+      state.discard();
     }
 
     let serialized = self.serialized;
     let length = self.length;
 
-    let state = self.state.transform(|inner| SliceSeed {
+    let state = state.transform(|inner| SliceSeed {
       serialized,
       length,
       inner,
@@ -125,7 +146,7 @@ impl<A: Allocator, C: Continuation<A>, V: View> AllocatedSliceSerializer<A, C, V
 
   fn end(mut self) -> C {
     if self.length != self.serialized {
-      todo!()
+      self.state.length_mismatch(self.length, self.serialized);
     }
 
     self.state.pop();
@@ -139,10 +160,15 @@ impl<A: Allocator, C: Continuation<A>, V: View> Continuation<A>
 {
   type Seed = SliceSeed<A, C>;
 
-  fn continuation(state: State<A, Self>) -> Self {
+  fn continuation(mut state: State<A, Self>) -> Self {
     let serialized = state.seed().serialized + 1;
     let length = state.seed().length;
 
+    // Stop discarding, if this element was past the end of the slice:
+    if serialized > length {
+      state.pop();
+    }
+
     AllocatedSliceSerializer {
       element: PhantomData,
       state: state.transform(|seed| seed.inner),
@@ -162,10 +188,12 @@ pub struct SliceSeed<A: Allocator, C: Continuation<A>> {
 mod tests {
   use super::*;
 
+  use crate::{ChunkedAllocator, FallibleVecAllocator, Message, ReuseAllocator};
+
   #[test]
   fn basic() {
     let slice: &[u8] = &[0u8, 1, 2, 3];
-    let serialized = slice.serialize_to_vec();
+    let serialized = slice.serialize_to_vec().unwrap();
 
     #[rustfmt::skip]
     assert_eq!(&serialized, &[
@@ -177,4 +205,175 @@ mod tests {
       0, 1, 2, 3
     ]);
   }
+
+  #[test]
+  fn nested() {
+    let slice: &[&[u8]] = &[&[7u8, 8]];
+    let serialized = slice.serialize_to_vec().unwrap();
+
+    #[rustfmt::skip]
+    assert_eq!(&serialized, &[
+      // outer offset
+      16, 0, 0, 0, 0, 0, 0, 0,
+      // outer length
+      1, 0, 0, 0, 0, 0, 0, 0,
+      // inner offset
+      16, 0, 0, 0, 0, 0, 0, 0,
+      // inner length
+      2, 0, 0, 0, 0, 0, 0, 0,
+      // elements
+      7, 8,
+    ]);
+
+    let view = <&[&[u8]]>::view(&serialized).unwrap();
+    assert_eq!(view.as_slice()[0].as_slice(), &[7, 8]);
+  }
+
+  fn lying<N: X>(elements: &[N], length: usize) -> Result<Vec<u8>, LengthMismatch> {
+    <Vec<N>>::store_to_vec()
+      .serialize_iterator(Lying {
+        inner: elements.iter(),
+        length,
+      })
+      .done()
+  }
+
+  #[test]
+  fn too_many() {
+    assert_eq!(
+      lying(&[0u8, 1, 2], 2),
+      Err(LengthMismatch {
+        expected: 2,
+        actual:   3,
+      })
+    );
+
+    let strings = [String::from("foo"), String::from("bar")];
+    assert_eq!(
+      lying(&strings, 1),
+      Err(LengthMismatch {
+        expected: 1,
+        actual:   2,
+      })
+    );
+  }
+
+  #[test]
+  fn too_few() {
+    assert_eq!(
+      lying(&[1u8, 2], 3),
+      Err(LengthMismatch {
+        expected: 3,
+        actual:   2,
+      })
+    );
+
+    assert_eq!(
+      lying(&[String::from("foo")], 2),
+      Err(LengthMismatch {
+        expected: 2,
+        actual:   1,
+      })
+    );
+  }
+
+  #[test]
+  fn overflow() {
+    assert_eq!(
+      lying(&[1u64, 2], usize::MAX),
+      Err(LengthMismatch {
+        expected: usize::MAX,
+        actual:   2,
+      })
+    );
+
+    assert_eq!(
+      lying(&[1u8, 2], usize::MAX - 8),
+      Err(LengthMismatch {
+        expected: usize::MAX - 8,
+        actual:   2,
+      })
+    );
+
+    // The outer slice fits, but the inner slice would end past the end of the
+    // address space:
+    let length = (usize::MAX - 32) / 16;
+    assert_eq!(
+      lying(&[vec![0u8; 32]], length),
+      Err(LengthMismatch {
+        expected: length,
+        actual:   1,
+      })
+    );
+  }
+
+  /// Serializes to a slice whose iterator reports the wrong length
+  struct Liar;
+
+  impl X for Liar {
+    type View = Slice<u8>;
+
+    fn serialize<A: Allocator, C: Continuation<A>>(
+      &self,
+      mut serializer: <Self::View as View>::Serializer<A, C>,
+    ) -> C {
+      serializer.serialize_iterator(Lying {
+        inner:  [1u8, 2].iter(),
+        length: 3,
+      })
+    }
+  }
+
+  #[test]
+  fn allocators() {
+    let want = LengthMismatch {
+      expected: 3,
+      actual:   2,
+    };
+
+    assert_eq!(Liar.serialize_to_vec(), Err(want));
+    assert_eq!(Liar.serialized_size(), Err(want));
+
+    let mut buffer = [0; 1024];
+    assert_eq!(
+      Liar
+        .serialize(Liar::store(SliceAllocator::new(&mut buffer)))
+        .done(),
+      Err(want)
+    );
+
+    assert_eq!(
+      Liar.serialize(Liar::store_to_slice(&mut buffer)).done(),
+      Err(Overflow::Mismatch(want))
+    );
+
+    let mut vec = Vec::new();
+    let mut stack = VecStack::new();
+    assert_eq!(
+      Liar
+        .serialize(Liar::store(ReuseAllocator::new(&mut vec, &mut stack)))
+        .done(),
+      Err(want)
+    );
+
+    assert_eq!(
+      Liar
+        .serialize(Liar::store(ChunkedAllocator::new()))
+        .done()
+        .err(),
+      Some(want)
+    );
+
+    assert_eq!(
+      Liar
+        .serialize(Liar::store(FallibleVecAllocator::new()))
+        .done(),
+      Err(ReserveError::Mismatch(want))
+    );
+
+    assert_eq!(
+      Message::<Liar>::new(&Liar).err(),
+      Some(Error::LengthMismatch { error: want })
+    );
+  }
 }
@@ -1,35 +1,67 @@
 use crate::common::*;
 
-// TODO: Should I also have a fallible slice allocator?
-pub struct SliceAllocator<'slice> {
+/// Serializes into a fixed-size buffer, panicking if the message does not fit,
+/// or is nested more than `DEPTH` levels deep. See `FallibleSliceAllocator` for
+/// a non-panicking alternative.
+pub struct SliceAllocator<'slice, const DEPTH: usize = DEFAULT_DEPTH> {
   slice: &'slice mut [u8],
-  // TODO: this can be removed
-  end:   usize,
 }
 
 impl<'slice> SliceAllocator<'slice> {
   pub fn new(slice: &'slice mut [u8]) -> SliceAllocator<'slice> {
-    Self { slice, end: 0 }
+    Self::with_depth(slice)
   }
 }
 
-impl<'slice> Allocator for SliceAllocator<'slice> {
+impl<'slice, const DEPTH: usize> SliceAllocator<'slice, DEPTH> {
+  pub fn with_depth(slice: &'slice mut [u8]) -> SliceAllocator<'slice, DEPTH> {
+    Self { slice }
+  }
+}
+
+impl<'slice, const DEPTH: usize> Allocator for SliceAllocator<'slice, DEPTH> {
+  type Error = LengthMismatch;
   type Output = &'slice [u8];
+  type Stack = ArrayStack<DEPTH>;
 
-  fn write(&mut self, bytes: &[u8], offset: usize) {
-    for (dst, src) in self.slice[offset..].iter_mut().zip(bytes) {
-      *dst = *src;
-    }
-    self.end = offset + bytes.len();
+  fn write(&mut self, bytes: &[u8], offset: usize) -> Result<(), Self::Error> {
+    self.slice[offset..offset + bytes.len()].copy_from_slice(bytes);
+    Ok(())
   }
 
-  fn finish(self, end: usize) -> Self::Output {
-    assert_eq!(self.end, end);
-    &self.slice[..end]
+  fn finish(self, end: usize) -> Result<Self::Output, Self::Error> {
+    Ok(&self.slice[..end])
+  }
+
+  fn length_mismatch(&mut self, error: LengthMismatch) -> Self::Error {
+    error
   }
 }
 
 #[cfg(test)]
 mod tests {
-  // TODO: needs tests
+  use super::*;
+
+  #[test]
+  fn nested() {
+    let mut buffer = [0; 1024];
+
+    round_trip(|native| {
+      <Vec<String>>::store(SliceAllocator::new(&mut buffer))
+        .serialize(native)
+        .done()
+        .unwrap()
+    });
+  }
+
+  #[test]
+  #[should_panic(expected = "Message nesting depth exceeded stack capacity of 2.")]
+  fn depth_exceeded() {
+    let mut buffer = [0; 1024];
+
+    <Vec<String>>::store(SliceAllocator::<2>::with_depth(&mut buffer))
+      .serialize(&vec![String::from("foo")])
+      .done()
+      .unwrap();
+  }
 }
@@ -0,0 +1,22 @@
+use crate::common::*;
+
+/// Storage for the regions of a message which have been allocated but not yet
+/// completely written, used by `State` to track where the next write should
+/// occur. The start of each region is the offset of the next write to that
+/// region.
+///
+/// Each level of out-of-line data nested inside a message requires one region,
+/// so the capacity of a stack limits how deeply nested a message can be.
+pub trait Stack: Default {
+  /// Push a region onto the stack, failing if the stack is full.
+  fn push(&mut self, region: Range<usize>) -> Result<(), DepthExceeded>;
+
+  fn pop(&mut self) -> Option<Range<usize>>;
+
+  fn clear(&mut self);
+
+  /// Regions currently on the stack, from bottom to top.
+  fn regions(&self) -> &[Range<usize>];
+
+  fn regions_mut(&mut self) -> &mut [Range<usize>];
+}
@@ -5,16 +5,33 @@ pub struct State<A: Allocator, C: Continuation<A>> {
   seed:         C::Seed,
   continuation: PhantomData<C>,
   end:          usize,
-  // TODO: This can't be a vec in no-alloc/no-std contexts
-  stack:        Vec<usize>,
+  committed:    usize,
+  stack:        A::Stack,
+  // Index of the first region on the stack which may have unwritten bytes
+  uncommitted:  usize,
+  // Number of regions pushed after the stack was full
+  skipped:      usize,
+  // Number of regions pushed while discarding an element which did not fit in
+  // its slice or array
+  discarded:    usize,
+  // First error returned by the allocator, after which it is no longer called
+  error:        Option<A::Error>,
 }
 
 impl<A: Allocator, C: Continuation<A>> State<A, C> {
-  pub fn new(allocator: A, seed: C::Seed) -> Self {
+  pub fn new(mut allocator: A, seed: C::Seed) -> Self {
+    let mut stack = allocator.stack();
+    stack.clear();
+
     Self {
       continuation: PhantomData,
       end: 0,
-      stack: Vec::new(),
+      committed: 0,
+      uncommitted: 0,
+      skipped: 0,
+      discarded: 0,
+      error: None,
+      stack,
       allocator,
       seed,
     }
@@ -33,7 +50,12 @@ impl<A: Allocator, C: Continuation<A>> State<A, C> {
     State {
       allocator:    self.allocator,
       end:          self.end,
+      committed:    self.committed,
       stack:        self.stack,
+      uncommitted:  self.uncommitted,
+      skipped:      self.skipped,
+      discarded:    self.discarded,
+      error:        self.error,
       seed:         transformer(self.seed),
       continuation: PhantomData,
     }
@@ -44,25 +66,148 @@ impl<A: Allocator, C: Continuation<A>> State<A, C> {
   }
 
   pub(crate) fn push(&mut self, size: usize) {
-    self.stack.push(self.end);
-    self.end += size;
+    // Discarded elements are not allocated space:
+    if self.discarded > 0 {
+      self.discarded += 1;
+      return;
+    }
+
+    // Regions which would end past the end of the address space are discarded.
+    // Only a slice serializer given the wrong length can push one, and it
+    // reports the mismatch when it ends.
+    let end = match self.end.checked_add(size) {
+      Some(end) => end,
+      None => {
+        self.discarded = 1;
+        return;
+      },
+    };
+
+    let region = self.end..end;
+    self.end = end;
+
+    // Once the stack is full, regions are counted so that pops can be matched
+    // with pushes, and writes to them are skipped.
+    if self.skipped > 0 {
+      self.skipped += 1;
+    } else if let Err(error) = self.stack.push(region) {
+      self.skipped = 1;
+
+      if self.error.is_none() {
+        let result = self.allocator.depth_exceeded(error);
+        self.fail(result);
+      }
+    }
   }
 
   pub(crate) fn pop(&mut self) {
-    self.stack.pop().unwrap();
+    if self.discarded > 0 {
+      self.discarded -= 1;
+    } else if self.skipped > 0 {
+      self.skipped -= 1;
+    } else {
+      self.stack.pop().unwrap();
+      self.uncommitted = self.uncommitted.min(self.stack.regions().len());
+    }
+  }
+
+  /// The offset at which the next write will occur.
+  pub(crate) fn position(&self) -> usize {
+    self
+      .stack
+      .regions()
+      .last()
+      .expect("State::position: Empty stack.")
+      .start
   }
 
   pub(crate) fn write(&mut self, bytes: &[u8]) {
-    if self.stack.is_empty() {
-      panic!("State::write: Empty stack.");
+    if self.skipped > 0 || self.discarded > 0 || self.error.is_some() {
+      return;
+    }
+
+    let region = self
+      .stack
+      .regions_mut()
+      .last_mut()
+      .expect("State::write: Empty stack.");
+    let result = self.allocator.write(bytes, region.start);
+    region.start += bytes.len();
+    self.fail(result);
+
+    self.commit();
+  }
+
+  /// Discard everything written until the matching `pop`, including regions
+  /// pushed in the meantime, which are not allocated.
+  pub(crate) fn discard(&mut self) {
+    self.discarded += 1;
+  }
+
+  /// Notify the allocator that a slice or array serializer was given the wrong
+  /// number of elements. Serialization fails with the error it returns.
+  pub(crate) fn length_mismatch(&mut self, expected: usize, actual: usize) {
+    if self.error.is_none() {
+      let error = self
+        .allocator
+        .length_mismatch(LengthMismatch { expected, actual });
+      self.error = Some(error);
     }
-    self.allocator.write(bytes, self.stack[0]);
-    self.stack[0] += bytes.len();
   }
 
-  pub(crate) fn finish(mut self) -> A::Output {
+  /// Save the error in `result`, if any, so that the allocator is not called
+  /// again, and the error is returned by `finish`.
+  fn fail(&mut self, result: Result<(), A::Error>) {
+    if let Err(error) = result {
+      self.error = Some(error);
+    }
+  }
+
+  /// Notify the allocator if the offset below which all bytes have been written
+  /// has advanced.
+  ///
+  /// Regions are allocated in increasing order, so everything before the
+  /// first region with unwritten bytes has been written. If there is no such
+  /// region, everything that has been allocated has been written.
+  ///
+  /// Only the last region on the stack is written to, so regions before
+  /// `uncommitted` stay written until they are popped, and the search for the
+  /// first region with unwritten bytes resumes from there.
+  fn commit(&mut self) {
+    if self.error.is_some() {
+      return;
+    }
+
+    let regions = self.stack.regions();
+
+    while let Some(region) = regions.get(self.uncommitted) {
+      if !region.is_empty() {
+        break;
+      }
+      self.uncommitted += 1;
+    }
+
+    let committed = regions
+      .get(self.uncommitted)
+      .map(|region| region.start)
+      .unwrap_or(self.end);
+
+    if committed > self.committed {
+      self.committed = committed;
+      let result = self.allocator.commit(committed);
+      self.fail(result);
+    }
+  }
+
+  pub(crate) fn finish(mut self) -> Result<A::Output, A::Error> {
     self.pop();
-    assert_eq!(self.stack.len(), 0);
+    assert!(self.stack.regions().is_empty());
+    self.allocator.reclaim(self.stack);
+
+    if let Some(error) = self.error {
+      return Err(error);
+    }
+
     self.allocator.finish(self.end)
   }
 
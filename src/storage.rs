@@ -0,0 +1,65 @@
+use crate::common::*;
+
+/// Owned bytes in which a `Message` can be stored.
+///
+/// # Safety
+///
+/// `bytes` must return the same bytes, at the same address, every time it is
+/// called, for as long as the storage is alive, even if the storage is moved,
+/// and `into_shared` must return an `Arc` containing those same bytes.
+/// `Message` validates the bytes once, and relies on this to skip validation
+/// on subsequent accesses.
+pub unsafe trait Storage {
+  fn bytes(&self) -> &[u8];
+
+  /// Convert the storage into an `Arc`. By default, the bytes are copied.
+  fn into_shared(self) -> Arc<[u8]>
+  where
+    Self: Sized,
+  {
+    Arc::from(self.bytes())
+  }
+}
+
+unsafe impl Storage for &[u8] {
+  fn bytes(&self) -> &[u8] {
+    self
+  }
+}
+
+unsafe impl Storage for Vec<u8> {
+  fn bytes(&self) -> &[u8] {
+    self
+  }
+
+  fn into_shared(self) -> Arc<[u8]> {
+    self.into()
+  }
+}
+
+unsafe impl Storage for Box<[u8]> {
+  fn bytes(&self) -> &[u8] {
+    self
+  }
+
+  fn into_shared(self) -> Arc<[u8]> {
+    self.into()
+  }
+}
+
+unsafe impl Storage for Arc<[u8]> {
+  fn bytes(&self) -> &[u8] {
+    self
+  }
+
+  fn into_shared(self) -> Arc<[u8]> {
+    self
+  }
+}
+
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+unsafe impl Storage for Mmap {
+  fn bytes(&self) -> &[u8] {
+    self
+  }
+}
@@ -21,32 +21,120 @@ pub struct StrSerializer<A: Allocator, C: Continuation<A>> {
 
 impl Str {
   pub fn as_str(&self) -> &str {
-    self.try_as_str().unwrap()
+    // Safe because references to a `Str` are only handed out after a full,
+    // non-shallow `check` has verified that its contents are valid UTF-8. A
+    // `LazyView<Str>` holds a shallowly checked `Str`, but only hands it out
+    // from `validate`, after a full check.
+    unsafe { str::from_utf8_unchecked(self.slice.as_slice()) }
   }
 
   fn try_as_str(&self) -> Result<&str> {
     Ok(str::from_utf8(self.slice.as_slice())?)
   }
+
+  /// The position of the contents of `suspect` in `buffer`. The slice of
+  /// `suspect` must have been checked.
+  fn contents(suspect: &MaybeUninit<Self>, buffer: &[u8]) -> Range<usize> {
+    // Safe because the slice has been checked.
+    let bytes = unsafe { suspect.cast::<Slice<u8>>().assume_init_ref() }.as_slice();
+    let start = bytes.as_ptr() as usize - buffer.as_ptr() as usize;
+    start..start + bytes.len()
+  }
+
+  /// Check the contents of a run of strings, the first of which is at `first`,
+  /// and whose contents are contiguous and occupy `run` in `buffer`.
+  ///
+  /// The run is valid if it is valid UTF-8 as a whole, and each string starts
+  /// on a char boundary. Otherwise, each string is checked in turn, to find the
+  /// first invalid one.
+  fn check_run(
+    suspects: &[MaybeUninit<Self>],
+    first: usize,
+    run: Range<usize>,
+    buffer: &[u8],
+  ) -> Result<()> {
+    if let Ok(contents) = str::from_utf8(&buffer[run.clone()]) {
+      if suspects
+        .iter()
+        .all(|suspect| contents.is_char_boundary(Self::contents(suspect, buffer).start - run.start))
+      {
+        return Ok(());
+      }
+    }
+
+    for (index, suspect) in suspects.iter().enumerate() {
+      // Safe because the slice has been checked.
+      unsafe { suspect.assume_init_ref() }
+        .try_as_str()
+        .map_err(|error| error.at(Segment::Index(first + index)))?;
+    }
+
+    Ok(())
+  }
 }
 
 impl<'a> From<&'a Str> for &'a str {
   fn from(view: &'a Str) -> Self {
-    view.try_as_str().unwrap()
+    view.as_str()
   }
 }
 
-impl View for Str {
+unsafe impl View for Str {
   type Serializer<A: Allocator, C: Continuation<A>> = StrSerializer<A, C>;
 
-  fn check<'value>(suspect: &'value MaybeUninit<Self>, buffer: &[u8]) -> Result<&'value Self> {
+  fn check(suspect: &MaybeUninit<Self>, validator: &mut Validator) -> Result<()> {
     let slice = suspect.cast::<Slice<u8>>();
-    View::check(slice, buffer)?;
+    View::check(slice, validator)?;
 
-    let value = unsafe { suspect.assume_init_ref() };
+    // Shallow checks only cover the in-line slice, see `View`:
+    if !validator.is_shallow() {
+      // Safe because the slice has been checked.
+      unsafe { suspect.assume_init_ref() }.try_as_str()?;
+    }
 
-    value.try_as_str()?;
+    Ok(())
+  }
 
-    Ok(value)
+  /// Check the contents of each run of strings which are contiguous, as they
+  /// are when serialized, with a single call to `str::from_utf8`, instead of
+  /// one call per string.
+  fn check_slice(suspects: &[MaybeUninit<Self>], validator: &mut Validator) -> Result<()> {
+    // Shallow checks only cover the in-line slices, see `View`:
+    if validator.is_shallow() {
+      for (index, suspect) in suspects.iter().enumerate() {
+        Self::check(suspect, validator).map_err(|error| error.at(Segment::Index(index)))?;
+      }
+
+      return Ok(());
+    }
+
+    let buffer = validator.buffer();
+
+    // Indices of the strings in the current run, and the position of their
+    // contents in the buffer:
+    let mut strings = 0..0;
+    let mut run = 0..0;
+
+    for (index, suspect) in suspects.iter().enumerate() {
+      if let Err(error) = View::check(suspect.cast::<Slice<u8>>(), validator) {
+        // Report an invalid string preceding this one first:
+        Self::check_run(&suspects[strings.clone()], strings.start, run, buffer)?;
+        return Err(error.at(Segment::Index(index)));
+      }
+
+      let contents = Self::contents(suspect, buffer);
+
+      if strings.is_empty() || contents.start != run.end {
+        Self::check_run(&suspects[strings.clone()], strings.start, run, buffer)?;
+        strings = index..index;
+        run = contents.start..contents.start;
+      }
+
+      strings.end = index + 1;
+      run.end = contents.end;
+    }
+
+    Self::check_run(&suspects[strings.clone()], strings.start, run, buffer)
   }
 }
 
@@ -95,4 +183,62 @@ mod tests {
       104, 101, 108, 108, 111,
     ]);
   }
+
+  #[test]
+  fn check_slice() {
+    let native = vec![
+      String::from("foo"),
+      String::new(),
+      String::from("\u{e9}"),
+      String::from("bar"),
+    ];
+
+    let buffer = native.serialize_to_vec().unwrap();
+    <Vec<String>>::view(&buffer).unwrap();
+
+    // Position of the header of each string, and of their contents:
+    let header = |index: usize| 16 + index * 16;
+    let contents = header(native.len());
+
+    // Invalid UTF-8 in the last string:
+    let mut invalid = buffer.clone();
+    invalid[contents + 5] = 0xFF;
+
+    assert_eq!(
+      <Vec<String>>::view(&invalid).err().unwrap(),
+      Error::String {
+        error: str::from_utf8(&invalid[contents + 5..]).unwrap_err(),
+      }
+      .at(Segment::Index(3))
+    );
+
+    // Move the start of the last string into the middle of the `é` before it,
+    // so that the contents are valid UTF-8 as a whole, but neither string is:
+    let mut split = buffer.clone();
+    split[header(2) + 8] -= 1;
+    split[header(3)] -= 1;
+    split[header(3) + 8] += 1;
+
+    assert_eq!(
+      <Vec<String>>::view(&split).err().unwrap(),
+      Error::String {
+        error: str::from_utf8(&split[contents + 3..contents + 4]).unwrap_err(),
+      }
+      .at(Segment::Index(2))
+    );
+
+    // Invalid UTF-8 in the first string is reported before an invalid offset
+    // in the last:
+    let mut both = buffer.clone();
+    both[contents] = 0xFF;
+    both[header(3)] = 0xFF;
+
+    assert_eq!(
+      <Vec<String>>::view(&both).err().unwrap(),
+      Error::String {
+        error: str::from_utf8(&both[contents..contents + 3]).unwrap_err(),
+      }
+      .at(Segment::Index(0))
+    );
+  }
 }
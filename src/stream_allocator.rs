@@ -0,0 +1,171 @@
+use crate::common::*;
+
+/// Minimum number of committed bytes to accumulate before writing them out.
+const CHUNK: usize = 1 << 16;
+
+/// Serializes into a writer which does not support seeking, such as a pipe,
+/// socket, or compressor.
+///
+/// Only the part of the message which has not yet been committed is kept in
+/// memory. Whenever enough committed bytes accumulate, they are written to the
+/// writer, and the rest of the message is written when serialization finishes.
+///
+/// Bytes are committed once everything before them has been written. Since the
+/// out-of-line contents of a slice's elements are written after the elements
+/// themselves, a slice of variable-length values, like `Vec<String>`, is kept
+/// in memory until its last element has been serialized.
+pub struct StreamAllocator<W: Write> {
+  writer:  W,
+  // Bytes of the message starting at `flushed`
+  window:  Vec<u8>,
+  flushed: usize,
+}
+
+impl<W: Write> StreamAllocator<W> {
+  pub fn new(writer: W) -> StreamAllocator<W> {
+    Self {
+      window: Vec::new(),
+      flushed: 0,
+      writer,
+    }
+  }
+
+  /// Write all bytes before `offset` to the writer.
+  fn flush(&mut self, offset: usize) -> io::Result<()> {
+    let flushable = offset - self.flushed;
+    self.writer.write_all(&self.window[..flushable])?;
+    self.window.drain(..flushable);
+    self.flushed = offset;
+    Ok(())
+  }
+}
+
+impl<W: Write> Allocator for StreamAllocator<W> {
+  type Error = io::Error;
+  type Output = W;
+  type Stack = VecStack;
+
+  fn write(&mut self, bytes: &[u8], offset: usize) -> Result<(), Self::Error> {
+    assert!(offset >= self.flushed);
+
+    self.window.place(bytes, offset - self.flushed);
+
+    Ok(())
+  }
+
+  fn commit(&mut self, offset: usize) -> Result<(), Self::Error> {
+    if offset - self.flushed < CHUNK {
+      return Ok(());
+    }
+
+    self.flush(offset)
+  }
+
+  fn finish(mut self, end: usize) -> Result<Self::Output, Self::Error> {
+    assert_eq!(self.flushed + self.window.len(), end);
+
+    self.flush(end)?;
+    self.writer.flush()?;
+
+    Ok(self.writer)
+  }
+
+  fn length_mismatch(&mut self, error: LengthMismatch) -> Self::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, error)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Writer that records the length of each write
+  #[derive(Default)]
+  struct Recorder {
+    bytes:  Vec<u8>,
+    writes: Vec<usize>,
+  }
+
+  impl Write for Recorder {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+      self.bytes.extend_from_slice(bytes);
+      self.writes.push(bytes.len());
+      Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+      Ok(())
+    }
+  }
+
+  impl AsRef<[u8]> for Recorder {
+    fn as_ref(&self) -> &[u8] {
+      &self.bytes
+    }
+  }
+
+  fn stream<N: X>(native: &N) -> Recorder {
+    N::store(StreamAllocator::new(Recorder::default()))
+      .serialize(native)
+      .done()
+      .unwrap()
+  }
+
+  #[test]
+  fn small() {
+    let recorder = round_trip(stream);
+
+    assert_eq!(recorder.writes, &[recorder.bytes.len()]);
+  }
+
+  #[test]
+  fn large() {
+    let native = (0..CHUNK * 4).map(|i| i as u8).collect::<Vec<u8>>();
+
+    let recorder = stream(&native);
+
+    assert_eq!(recorder.bytes, native.serialize_to_vec().unwrap());
+    assert!(recorder.writes.len() > 1);
+    assert!(recorder.writes.iter().all(|&len| len < CHUNK * 2));
+  }
+
+  #[test]
+  fn error() {
+    struct Broken;
+
+    impl Write for Broken {
+      fn write(&mut self, _bytes: &[u8]) -> io::Result<usize> {
+        Err(io::Error::other("broken"))
+      }
+
+      fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+      }
+    }
+
+    let error = <Vec<u8>>::store(StreamAllocator::new(Broken))
+      .serialize(&vec![0u8; CHUNK * 2])
+      .done()
+      .err()
+      .unwrap();
+
+    assert_eq!(error.kind(), io::ErrorKind::Other);
+  }
+
+  #[test]
+  fn length_mismatch() {
+    let error = <Vec<u8>>::store(StreamAllocator::new(Vec::new()))
+      .serialize_iterator(Lying {
+        inner:  [0u8, 1, 2].iter(),
+        length: 2,
+      })
+      .done()
+      .unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(
+      error.to_string(),
+      "Expected 2 elements but 3 were serialized"
+    );
+  }
+}
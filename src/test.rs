@@ -1,7 +1,7 @@
 use crate::common::*;
 
 pub(crate) fn ok<Native: Eq + Debug + FromView>(native: Native, want: &[u8]) {
-  let have = native.serialize_to_vec();
+  let have = native.serialize_to_vec().unwrap();
   assert_eq!(have, want);
   let view = Native::view(&have).unwrap();
   let round_tripped = Native::from_view(view);
@@ -9,7 +9,7 @@ pub(crate) fn ok<Native: Eq + Debug + FromView>(native: Native, want: &[u8]) {
 }
 
 pub(crate) fn ok_serialize<Native: X + Eq + Debug>(native: Native, want: &[u8]) {
-  let have = native.serialize_to_vec();
+  let have = native.serialize_to_vec().unwrap();
   assert_eq!(have, want);
   Native::view(&have).unwrap();
 }
@@ -18,3 +18,40 @@ pub(crate) fn err<V: View + Debug>(bytes: &[u8], want: Error) {
   let have = V::load(&bytes).unwrap_err();
   assert_eq!(have, want);
 }
+
+/// A message with nested out-of-line data, for testing allocators.
+pub(crate) fn strings() -> Vec<String> {
+  vec![String::from("foo"), String::from("bar")]
+}
+
+/// Serialize `strings()` with `serialize`, check that the output is the same as
+/// `serialize_to_vec`'s and loads back to the original message, and return it
+/// for allocator-specific checks.
+pub(crate) fn round_trip<O: AsRef<[u8]>>(serialize: impl FnOnce(&Vec<String>) -> O) -> O {
+  let native = strings();
+  let have = serialize(&native);
+  assert_eq!(have.as_ref(), native.serialize_to_vec().unwrap().as_slice());
+  let view = <Vec<String>>::view(have.as_ref()).unwrap();
+  assert_eq!(<Vec<String>>::from_view(view), native);
+  have
+}
+
+/// An iterator which reports the wrong length.
+pub(crate) struct Lying<I> {
+  pub(crate) inner:  I,
+  pub(crate) length: usize,
+}
+
+impl<I: Iterator> Iterator for Lying<I> {
+  type Item = I::Item;
+
+  fn next(&mut self) -> Option<I::Item> {
+    self.inner.next()
+  }
+}
+
+impl<I: Iterator> ExactSizeIterator for Lying<I> {
+  fn len(&self) -> usize {
+    self.length
+  }
+}
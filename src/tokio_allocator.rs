@@ -0,0 +1,142 @@
+use crate::common::*;
+
+/// Serializes into a `tokio` writer, such as a `tokio::fs::File`.
+///
+/// Serialization is synchronous, so writes are buffered, and are performed
+/// without blocking the executor by awaiting `TokioWrites::finish` on the
+/// output. As with `FileAllocator`, the writer is only seeked when a write
+/// does not immediately follow the previous one.
+pub struct TokioAllocator<W: AsyncWrite + AsyncSeek + Unpin> {
+  writer: W,
+  // Runs of contiguous bytes, and the offsets at which they start
+  writes: Vec<(usize, Vec<u8>)>,
+}
+
+impl<W: AsyncWrite + AsyncSeek + Unpin> TokioAllocator<W> {
+  pub fn new(writer: W) -> TokioAllocator<W> {
+    Self {
+      writes: Vec::new(),
+      writer,
+    }
+  }
+}
+
+impl<W: AsyncWrite + AsyncSeek + Unpin> Allocator for TokioAllocator<W> {
+  type Error = io::Error;
+  type Output = TokioWrites<W>;
+  type Stack = VecStack;
+
+  fn write(&mut self, bytes: &[u8], offset: usize) -> Result<(), Self::Error> {
+    if let Some((start, run)) = self.writes.last_mut() {
+      if *start + run.len() == offset {
+        run.extend_from_slice(bytes);
+        return Ok(());
+      }
+    }
+
+    self.writes.push((offset, bytes.to_vec()));
+
+    Ok(())
+  }
+
+  fn finish(self, end: usize) -> Result<Self::Output, Self::Error> {
+    Ok(TokioWrites {
+      writer: self.writer,
+      writes: self.writes,
+      end,
+    })
+  }
+
+  fn length_mismatch(&mut self, error: LengthMismatch) -> Self::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, error)
+  }
+}
+
+/// Buffered writes of a message serialized with `TokioAllocator`.
+#[must_use]
+pub struct TokioWrites<W: AsyncWrite + AsyncSeek + Unpin> {
+  writer: W,
+  writes: Vec<(usize, Vec<u8>)>,
+  end:    usize,
+}
+
+impl<W: AsyncWrite + AsyncSeek + Unpin> TokioWrites<W> {
+  /// Length of the message, in bytes.
+  pub fn len(&self) -> usize {
+    self.end
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.end == 0
+  }
+
+  /// Write the message to the writer, flush it, and return it.
+  pub async fn finish(mut self) -> io::Result<W> {
+    let mut position = 0;
+
+    for (offset, bytes) in &self.writes {
+      if position != *offset {
+        self.writer.seek(SeekFrom::Start(offset.to_u64())).await?;
+      }
+
+      self.writer.write_all(bytes).await?;
+
+      position = offset + bytes.len();
+    }
+
+    self.writer.flush().await?;
+
+    Ok(self.writer)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use std::io::Cursor;
+
+  fn store<N: X>(native: N) -> Vec<u8> {
+    let writes = N::store(TokioAllocator::new(Cursor::new(Vec::new())))
+      .serialize(&native)
+      .done()
+      .unwrap();
+
+    assert_eq!(writes.len(), native.serialized_size().unwrap());
+
+    tokio::runtime::Builder::new_current_thread()
+      .build()
+      .unwrap()
+      .block_on(writes.finish())
+      .unwrap()
+      .into_inner()
+  }
+
+  #[test]
+  fn empty() {
+    assert_eq!(store(()), &[]);
+  }
+
+  #[test]
+  fn out_of_order() {
+    round_trip(|native| store(native));
+  }
+
+  #[test]
+  fn length_mismatch() {
+    let error = <Vec<u8>>::store(TokioAllocator::new(Cursor::new(Vec::new())))
+      .serialize_iterator(Lying {
+        inner:  [0u8, 1].iter(),
+        length: 3,
+      })
+      .done()
+      .err()
+      .unwrap();
+
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(
+      error.to_string(),
+      "Expected 3 elements but 2 were serialized"
+    );
+  }
+}
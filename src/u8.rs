@@ -22,12 +22,17 @@ impl FromView for u8 {
   }
 }
 
-impl View for u8 {
+unsafe impl View for u8 {
   type Serializer<A: Allocator, C: Continuation<A>> = U8Serializer<A, C>;
 
-  fn check<'value>(suspect: &'value MaybeUninit<Self>, _buffer: &[u8]) -> Result<&'value Self> {
+  fn check(_suspect: &MaybeUninit<Self>, _validator: &mut Validator) -> Result<()> {
     // All bit patterns of the correct size are valid values of type Self.
-    Ok(unsafe { suspect.assume_init_ref() })
+    Ok(())
+  }
+
+  fn check_slice(_suspects: &[MaybeUninit<Self>], _validator: &mut Validator) -> Result<()> {
+    // All bit patterns are valid, so there is nothing to check.
+    Ok(())
   }
 }
 
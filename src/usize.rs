@@ -25,10 +25,10 @@ impl FromView for usize {
   }
 }
 
-impl View for Usize {
+unsafe impl View for Usize {
   type Serializer<A: Allocator, C: Continuation<A>> = UsizeSerializer<A, C>;
 
-  fn check<'value>(suspect: &'value MaybeUninit<Self>, buffer: &[u8]) -> Result<&'value Self> {
+  fn check(suspect: &MaybeUninit<Self>, validator: &mut Validator) -> Result<()> {
     let struct_pointer: *const Usize = suspect.as_ptr();
 
     let inner_pointer = struct_pointer as *const U64;
@@ -41,27 +41,25 @@ impl View for Usize {
     // - Valid bitpattern: All bitpatterns are valid for MaybeUninit.
     let inner_suspect = unsafe { &*inner_suspect_pointer };
 
-    View::check(inner_suspect, buffer)?;
-
-    // Safe because inner has been checked.
-    let reference = unsafe { suspect.assume_init_ref() };
+    let inner = inner_suspect.check(validator)?;
 
     // Enforce the additional invariant that Usize values may not be larger than can
     // fit in the native `usize` type.
 
-    let value = reference.inner.to_native();
+    let value = inner.to_native();
 
     // TODO: There isn't a test for this
     if value > usize::MAX.to_u64() {
       return Err(Error::Usize { value });
     }
 
-    Ok(reference)
+    Ok(())
   }
 }
 
 impl From<&Usize> for usize {
   fn from(view: &Usize) -> usize {
+    // Does not truncate, since `check` verified that the value fits in a usize.
     u64::from(&view.inner) as usize
   }
 }
@@ -0,0 +1,583 @@
+use crate::common::*;
+
+/// Number of elements above which slices are checked in parallel, and the
+/// number of elements checked by each task.
+#[cfg(all(feature = "std", feature = "rayon"))]
+const PARALLEL_THRESHOLD: usize = 1024;
+
+/// The buffer being validated by `View::check`, and the work done so far,
+/// which is limited by the `LoadOptions` passed to `View::load_with`.
+#[derive(Clone)]
+pub struct Validator<'buffer> {
+  buffer:   &'buffer [u8],
+  options:  LoadOptions,
+  depth:    usize,
+  elements: usize,
+  bytes:    usize,
+  // End of the last region allocated, checked in canonical mode
+  end:      usize,
+  // Whether to skip checking out-of-line data, used by `LazyView`
+  shallow:  bool,
+  // Totals shared with other validators checking the same slice in parallel,
+  // which are used instead of `elements` and `bytes` if present
+  #[cfg(all(feature = "std", feature = "rayon"))]
+  shared:   Option<Arc<SharedTotals>>,
+}
+
+impl<'buffer> Validator<'buffer> {
+  pub fn new(buffer: &'buffer [u8], options: &LoadOptions) -> Validator<'buffer> {
+    Self {
+      options: *options,
+      depth: 0,
+      elements: 0,
+      bytes: 0,
+      end: 0,
+      shallow: false,
+      #[cfg(all(feature = "std", feature = "rayon"))]
+      shared: None,
+      buffer,
+    }
+  }
+
+  /// Create a validator which checks views, and the offsets they contain, but
+  /// not the out-of-line data those offsets point to. Canonical encoding is not
+  /// checked, since it depends on the out-of-line data.
+  pub(crate) fn shallow(buffer: &'buffer [u8], options: &LoadOptions) -> Validator<'buffer> {
+    Self {
+      shallow: true,
+      ..Self::new(buffer, &LoadOptions {
+        canonical: false,
+        ..*options
+      })
+    }
+  }
+
+  pub(crate) fn is_shallow(&self) -> bool {
+    self.shallow
+  }
+
+  pub fn buffer(&self) -> &'buffer [u8] {
+    self.buffer
+  }
+
+  /// Follow an offset to `elements` elements, occupying `bytes` bytes, failing
+  /// if doing so would exceed a limit. Must be paired with a call to `exit`
+  /// once the elements have been checked.
+  pub(crate) fn enter(&mut self, elements: usize, bytes: usize) -> Result<()> {
+    if self.depth == self.options.max_depth {
+      return Err(Error::DepthLimit {
+        maximum: self.options.max_depth,
+      });
+    }
+
+    self.count(elements, bytes)?;
+
+    self.depth += 1;
+
+    Ok(())
+  }
+
+  pub(crate) fn exit(&mut self) {
+    self.depth -= 1;
+  }
+
+  /// Add `elements` and `bytes` to the totals, failing if either exceeds its
+  /// limit.
+  fn count(&mut self, elements: usize, bytes: usize) -> Result<()> {
+    #[cfg(all(feature = "std", feature = "rayon"))]
+    if let Some(shared) = &self.shared {
+      return shared.count(elements, bytes, &self.options);
+    }
+
+    self.elements = self
+      .elements
+      .checked_add(elements)
+      .filter(|&total| total <= self.options.max_elements)
+      .ok_or(Error::ElementLimit {
+        maximum: self.options.max_elements,
+      })?;
+
+    self.bytes = self
+      .bytes
+      .checked_add(bytes)
+      .filter(|&total| total <= self.options.max_bytes)
+      .ok_or(Error::ByteLimit {
+        maximum: self.options.max_bytes,
+      })?;
+
+    Ok(())
+  }
+
+  /// Check the elements pointed to by an offset, returning the error of the
+  /// first invalid element.
+  pub(crate) fn check_elements<V: View>(&mut self, elements: &[MaybeUninit<V>]) -> Result<()> {
+    #[cfg(all(feature = "std", feature = "rayon"))]
+    return <V as CheckElements>::check_elements(elements, self);
+
+    #[cfg(not(all(feature = "std", feature = "rayon")))]
+    V::check_slice(elements, self)
+  }
+
+  /// Check `elements` in chunks, in parallel. Each chunk is checked with a copy
+  /// of this validator, and the copies share their totals, so that limits
+  /// apply to the work done by all of them together.
+  #[cfg(all(feature = "std", feature = "rayon"))]
+  fn check_parallel<V: View + Sync>(&mut self, elements: &[MaybeUninit<V>]) -> Result<()> {
+    let shared = match &self.shared {
+      Some(shared) => shared.clone(),
+      None => Arc::new(SharedTotals::new(self.elements, self.bytes)),
+    };
+
+    let mut parent = self.clone();
+    parent.shared = Some(shared.clone());
+
+    // Return the error of the first chunk which failed. Once a chunk has
+    // failed, chunks after it are no longer started:
+    let error = elements
+      .par_chunks(PARALLEL_THRESHOLD)
+      .enumerate()
+      .find_map_first(|(chunk, elements)| {
+        V::check_slice(elements, &mut parent.clone())
+          .err()
+          .map(|error| error.rebase(chunk * PARALLEL_THRESHOLD))
+      });
+
+    if self.shared.is_none() {
+      self.elements = shared.elements();
+      self.bytes = shared.bytes();
+    }
+
+    match error {
+      Some(error) => Err(error),
+      None => Ok(()),
+    }
+  }
+
+  /// In canonical mode, check that `region` is where serialization would have
+  /// placed the next out-of-line data, immediately after the previous.
+  ///
+  /// Serialization allocates space for out-of-line data when writing the offset
+  /// that points to it, and views are checked in the same order that they are
+  /// serialized, so this rejects out-of-order, overlapping, and non-contiguous
+  /// data.
+  pub(crate) fn allocate(&mut self, region: Range<usize>) -> Result<()> {
+    if !self.options.canonical {
+      return Ok(());
+    }
+
+    if region.start != self.end {
+      return Err(Error::CanonicalOffset {
+        expected: self.end,
+        actual:   region.start,
+      });
+    }
+
+    self.end = region.end;
+
+    Ok(())
+  }
+
+  /// In canonical mode, check that `padding` is all zeros.
+  pub(crate) fn padding(&self, padding: &[u8]) -> Result<()> {
+    if !self.options.canonical {
+      return Ok(());
+    }
+
+    match padding.iter().find(|&&byte| byte != 0) {
+      Some(&value) => Err(Error::Padding { value }),
+      None => Ok(()),
+    }
+  }
+
+  /// In canonical mode, check that the buffer ends with the last region
+  /// allocated.
+  pub(crate) fn finish(&self) -> Result<()> {
+    if self.options.canonical && self.end != self.buffer.len() {
+      return Err(Error::TrailingBytes { end: self.end });
+    }
+
+    Ok(())
+  }
+}
+
+/// Checks the elements pointed to by an offset in parallel if they can be
+/// shared between threads, and on the current thread otherwise.
+#[cfg(all(feature = "std", feature = "rayon"))]
+trait CheckElements: View {
+  fn check_elements(elements: &[MaybeUninit<Self>], validator: &mut Validator) -> Result<()>;
+}
+
+#[cfg(all(feature = "std", feature = "rayon"))]
+impl<V: View> CheckElements for V {
+  default fn check_elements(
+    elements: &[MaybeUninit<Self>],
+    validator: &mut Validator,
+  ) -> Result<()> {
+    V::check_slice(elements, validator)
+  }
+}
+
+#[cfg(all(feature = "std", feature = "rayon"))]
+impl<V: View + Sync> CheckElements for V {
+  fn check_elements(elements: &[MaybeUninit<Self>], validator: &mut Validator) -> Result<()> {
+    // Canonical mode requires that elements are checked in order.
+    if elements.len() > PARALLEL_THRESHOLD && !validator.options.canonical {
+      return validator.check_parallel(elements);
+    }
+
+    V::check_slice(elements, validator)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn load<N: X>(native: N, options: LoadOptions) -> Result<()> {
+    let buffer = native.serialize_to_vec().unwrap();
+    N::View::load_with(&buffer, &options).map(|_| ())
+  }
+
+  #[test]
+  fn unlimited() {
+    load(vec![vec![1u8, 2], vec![3]], LoadOptions::default()).unwrap();
+  }
+
+  #[test]
+  fn depth() {
+    let native = vec![vec![String::from("a")]];
+
+    let options = LoadOptions {
+      max_depth: 3,
+      ..LoadOptions::default()
+    };
+
+    load(native.clone(), options).unwrap();
+
+    let options = LoadOptions {
+      max_depth: 2,
+      ..LoadOptions::default()
+    };
+
+    assert_eq!(
+      load(native, options).unwrap_err().kind(),
+      &Error::DepthLimit { maximum: 2 }
+    );
+  }
+
+  #[test]
+  fn elements() {
+    let native = vec![vec![1u8, 2], vec![3]];
+
+    let options = LoadOptions {
+      max_elements: 5,
+      ..LoadOptions::default()
+    };
+
+    load(native.clone(), options).unwrap();
+
+    let options = LoadOptions {
+      max_elements: 4,
+      ..LoadOptions::default()
+    };
+
+    assert_eq!(
+      load(native, options).unwrap_err().kind(),
+      &Error::ElementLimit { maximum: 4 }
+    );
+  }
+
+  #[test]
+  fn bytes() {
+    let native = vec![vec![1u8, 2], vec![3]];
+
+    let options = LoadOptions {
+      max_bytes: 35,
+      ..LoadOptions::default()
+    };
+
+    load(native.clone(), options).unwrap();
+
+    let options = LoadOptions {
+      max_bytes: 34,
+      ..LoadOptions::default()
+    };
+
+    assert_eq!(
+      load(native, options).unwrap_err().kind(),
+      &Error::ByteLimit { maximum: 34 }
+    );
+  }
+
+  #[test]
+  #[rustfmt::skip]
+  fn overlapping() {
+    // Two slices pointing to the same four elements
+    let buffer = &[
+      // offset
+      16, 0, 0, 0, 0, 0, 0, 0,
+      // length
+      2, 0, 0, 0, 0, 0, 0, 0,
+      // first slice offset
+      32, 0, 0, 0, 0, 0, 0, 0,
+      // first slice length
+      4, 0, 0, 0, 0, 0, 0, 0,
+      // second slice offset
+      16, 0, 0, 0, 0, 0, 0, 0,
+      // second slice length
+      4, 0, 0, 0, 0, 0, 0, 0,
+      // elements
+      1, 2, 3, 4,
+    ];
+
+    <Vec<Vec<u8>>>::view(buffer).unwrap();
+
+    let options = LoadOptions {
+      max_elements: 9,
+      ..LoadOptions::default()
+    };
+
+    assert_eq!(
+      Slice::<Slice<u8>>::load_with(buffer, &options).err().unwrap().kind(),
+      &Error::ElementLimit { maximum: 9 }
+    );
+  }
+
+  #[test]
+  fn large() {
+    let mut native = (0..5000).map(|i| i.to_string()).collect::<Vec<String>>();
+    native[1000] = String::from("a");
+    native[4000] = String::from("b");
+
+    let mut buffer = native.serialize_to_vec().unwrap();
+    load(native, LoadOptions::default()).unwrap();
+
+    // Corrupt both strings, the first of which should be reported:
+    for &byte in b"ab" {
+      let position = buffer.iter().rposition(|&have| have == byte).unwrap();
+      buffer[position] = 0xFF;
+    }
+
+    let error = <Vec<String>>::view(&buffer).err().unwrap();
+
+    let path = error.path().collect::<Vec<Segment>>();
+    assert_eq!(path, &[Segment::Index(1000)]);
+    assert!(matches!(error.kind(), Error::String { .. }));
+  }
+
+  #[test]
+  fn large_limit() {
+    let native = vec![vec![0u8; 2]; 5000];
+
+    let options = LoadOptions {
+      max_elements: 5000 + 5000 * 2,
+      ..LoadOptions::default()
+    };
+
+    load(native.clone(), options).unwrap();
+
+    let options = LoadOptions {
+      max_elements: 5000 + 5000 * 2 - 1,
+      ..LoadOptions::default()
+    };
+
+    assert_eq!(
+      load(native, options).unwrap_err().kind(),
+      &Error::ElementLimit {
+        maximum: 5000 + 5000 * 2 - 1,
+      }
+    );
+  }
+
+  #[test]
+  #[cfg(all(feature = "std", feature = "rayon"))]
+  fn parallel_totals() {
+    let buffer = vec![vec![0u8; 2]; 5000].serialize_to_vec().unwrap();
+
+    let mut validator = Validator::new(&buffer, &LoadOptions::default());
+
+    Slice::<Slice<u8>>::cast(&buffer, 0)
+      .unwrap()
+      .check(&mut validator)
+      .unwrap();
+
+    assert!(validator.shared.is_none());
+    assert_eq!(validator.elements, 5000 + 5000 * 2);
+    assert_eq!(validator.bytes, 5000 * 16 + 5000 * 2);
+  }
+
+  #[test]
+  #[cfg(all(feature = "std", feature = "rayon"))]
+  fn parallel_limit() {
+    // Each chunk is within the limit on its own, but not all of them together:
+    let options = LoadOptions {
+      max_elements: 5000 + PARALLEL_THRESHOLD * 2,
+      ..LoadOptions::default()
+    };
+
+    let error = load(vec![vec![0u8; 2]; 5000], options).unwrap_err();
+
+    assert_eq!(error.kind(), &Error::ElementLimit {
+      maximum: 5000 + PARALLEL_THRESHOLD * 2,
+    });
+
+    // The limit is exceeded while checking an element, and not only once all
+    // chunks have been checked:
+    assert!(error.path().next().is_some());
+  }
+
+  #[test]
+  #[cfg(all(feature = "std", feature = "rayon"))]
+  fn parallel_first_error() {
+    let mut buffer = vec![true; 5000].serialize_to_vec().unwrap();
+    buffer[16 + 1500] = 2;
+    buffer[16 + 4500] = 3;
+
+    let error = Slice::<bool>::load(&buffer).err().unwrap();
+
+    assert_eq!(error, Error::Bool { value: 2 }.at(Segment::Index(1500)));
+  }
+
+  #[test]
+  #[cfg(all(feature = "std", feature = "rayon"))]
+  fn parallel_unsync() {
+    use core::cell::Cell;
+
+    /// View which can't be shared between threads, so its slices are checked
+    /// on the current thread
+    #[repr(C)]
+    struct Unsync {
+      value: u8,
+      cell:  PhantomData<Cell<u8>>,
+    }
+
+    unsafe impl View for Unsync {
+      type Serializer<A: Allocator, C: Continuation<A>> = <u8 as View>::Serializer<A, C>;
+
+      fn check(suspect: &MaybeUninit<Self>, validator: &mut Validator) -> Result<()> {
+        bool::check(suspect.cast(), validator)
+      }
+    }
+
+    let mut buffer = vec![true; 5000].serialize_to_vec().unwrap();
+    buffer[16 + 1500] = 2;
+
+    let error = Slice::<Unsync>::load(&buffer).err().unwrap();
+
+    assert_eq!(error, Error::Bool { value: 2 }.at(Segment::Index(1500)));
+  }
+
+  fn canonical<N: X>(native: N) {
+    let buffer = native.serialize_to_vec().unwrap();
+    N::View::load_canonical(&buffer).unwrap();
+  }
+
+  #[test]
+  fn serialization_is_canonical() {
+    canonical(());
+    canonical(1u32);
+    canonical(Some(1u8));
+    canonical(core::option::Option::<u64>::None);
+    canonical(core::result::Result::<u8, u64>::Ok(1));
+    canonical(core::result::Result::<u8, u64>::Err(1));
+    canonical(String::from("hello"));
+    canonical(vec![
+      String::from("foo"),
+      String::new(),
+      String::from("bar"),
+    ]);
+    canonical(vec![vec![1u8, 2], vec![], vec![3]]);
+    canonical([true, false].iter().copied().collect::<BitVec>());
+  }
+
+  #[test]
+  fn trailing_bytes() {
+    let mut buffer = vec![1u8, 2].serialize_to_vec().unwrap();
+    buffer.push(0);
+
+    <Vec<u8>>::view(&buffer).unwrap();
+
+    assert_eq!(
+      Slice::<u8>::load_canonical(&buffer).err().unwrap(),
+      Error::TrailingBytes { end: 18 }
+    );
+  }
+
+  #[test]
+  #[rustfmt::skip]
+  fn gap() {
+    let buffer = &[
+      // offset
+      17, 0, 0, 0, 0, 0, 0, 0,
+      // length
+      1, 0, 0, 0, 0, 0, 0, 0,
+      // gap
+      0,
+      // elements
+      1,
+    ];
+
+    <Vec<u8>>::view(buffer).unwrap();
+
+    assert_eq!(
+      Slice::<u8>::load_canonical(buffer).err().unwrap(),
+      Error::CanonicalOffset { expected: 16, actual: 17 }
+    );
+  }
+
+  #[test]
+  #[rustfmt::skip]
+  fn out_of_order() {
+    let buffer = &[
+      // offset
+      16, 0, 0, 0, 0, 0, 0, 0,
+      // length
+      2, 0, 0, 0, 0, 0, 0, 0,
+      // first slice offset
+      33, 0, 0, 0, 0, 0, 0, 0,
+      // first slice length
+      1, 0, 0, 0, 0, 0, 0, 0,
+      // second slice offset
+      16, 0, 0, 0, 0, 0, 0, 0,
+      // second slice length
+      1, 0, 0, 0, 0, 0, 0, 0,
+      // elements of second slice
+      2,
+      // elements of first slice
+      1,
+    ];
+
+    let view = <Vec<Vec<u8>>>::view(buffer).unwrap();
+    assert_eq!(<Vec<Vec<u8>>>::from_view(view), vec![vec![1], vec![2]]);
+
+    assert_eq!(
+      Slice::<Slice<u8>>::load_canonical(buffer).err().unwrap().kind(),
+      &Error::CanonicalOffset { expected: 48, actual: 49 }
+    );
+  }
+
+  #[test]
+  fn padding() {
+    let none: &[u8] = &[0, 1, 0];
+
+    <core::option::Option<u16>>::view(none).unwrap();
+
+    assert_eq!(
+      <<core::option::Option<u16> as X>::View>::load_canonical(none)
+        .err()
+        .unwrap(),
+      Error::Padding { value: 1 }
+    );
+
+    let ok: &[u8] = &[0, 1, 5];
+
+    <core::result::Result<u8, u16>>::view(ok).unwrap();
+
+    assert_eq!(
+      <<core::result::Result<u8, u16> as X>::View>::load_canonical(ok)
+        .err()
+        .unwrap(),
+      Error::Padding { value: 5 }
+    );
+  }
+}
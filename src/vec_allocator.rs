@@ -9,17 +9,32 @@ impl VecAllocator {
   pub fn new() -> VecAllocator {
     VecAllocator::default()
   }
+
+  /// Create a new allocator with space for `capacity` bytes reserved up front,
+  /// for example with the result of `X::serialized_size`.
+  pub fn with_capacity(capacity: usize) -> VecAllocator {
+    VecAllocator {
+      vec: Vec::with_capacity(capacity),
+    }
+  }
 }
 
 impl Allocator for VecAllocator {
+  type Error = LengthMismatch;
   type Output = Vec<u8>;
+  type Stack = VecStack;
 
-  fn write(&mut self, bytes: &[u8], offset: usize) {
+  fn write(&mut self, bytes: &[u8], offset: usize) -> Result<(), Self::Error> {
     self.vec.place(bytes, offset);
+    Ok(())
   }
 
-  fn finish(self, end: usize) -> Self::Output {
+  fn finish(self, end: usize) -> Result<Self::Output, Self::Error> {
     assert_eq!(self.vec.len(), end);
-    self.vec
+    Ok(self.vec)
+  }
+
+  fn length_mismatch(&mut self, error: LengthMismatch) -> Self::Error {
+    error
   }
 }
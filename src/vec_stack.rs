@@ -0,0 +1,39 @@
+use crate::common::*;
+
+/// A stack with no fixed capacity, backed by a `Vec`.
+///
+/// A stack can be reused across messages to avoid allocating a new one for
+/// each message. See `ReuseAllocator`.
+#[derive(Default)]
+pub struct VecStack {
+  regions: Vec<Range<usize>>,
+}
+
+impl VecStack {
+  pub fn new() -> VecStack {
+    VecStack::default()
+  }
+}
+
+impl Stack for VecStack {
+  fn push(&mut self, region: Range<usize>) -> Result<(), DepthExceeded> {
+    self.regions.push(region);
+    Ok(())
+  }
+
+  fn pop(&mut self) -> Option<Range<usize>> {
+    self.regions.pop()
+  }
+
+  fn clear(&mut self) {
+    self.regions.clear();
+  }
+
+  fn regions(&self) -> &[Range<usize>] {
+    &self.regions
+  }
+
+  fn regions_mut(&mut self) -> &mut [Range<usize>] {
+    &mut self.regions
+  }
+}
@@ -1,7 +1,26 @@
 use crate::common::*;
 
 // TODO: Impl X for all View
-pub trait View: Sized {
+/// A type which can be loaded from an untrusted buffer.
+///
+/// # Safety
+///
+/// `check` and `check_slice` must only succeed if their suspects are valid
+/// values of `Self`, since views are loaded from buffers by assuming that the
+/// suspects which they check successfully are initialized.
+///
+/// The one exception is a shallow check, used by `LazyView`, for which
+/// `Validator::is_shallow` returns true. Shallow checks skip out-of-line data,
+/// so they only establish that the in-line part of a suspect is valid, such as
+/// the offset and length of a `Str`, but not that its contents are UTF-8. A
+/// reference produced by a shallow check may be used within the crate to reach
+/// the data it points to, but must never be handed out until it has passed a
+/// full check.
+///
+/// Views must also have alignment 1, and so contain no padding, since they are
+/// referenced in place at arbitrary offsets within byte buffers, and every byte
+/// of a view that passes its check is assumed to be initialized.
+pub unsafe trait View: Sized {
   type Serializer<A: Allocator, C: Continuation<A>>: Serializer<A, C>;
 
   fn to_native<N: X<View = Self> + FromView>(&self) -> N {
@@ -9,20 +28,32 @@ pub trait View: Sized {
   }
 
   fn load(buffer: &[u8]) -> Result<&Self> {
+    Self::load_with(buffer, &LoadOptions::default())
+  }
+
+  /// Load a view from `buffer`, failing if `buffer` is not the canonical
+  /// encoding of the view's value, which is the encoding produced by
+  /// serialization. Two buffers loaded this way are equal if and only if their
+  /// values are equal.
+  fn load_canonical(buffer: &[u8]) -> Result<&Self> {
+    Self::load_with(buffer, &LoadOptions {
+      canonical: true,
+      ..LoadOptions::default()
+    })
+  }
+
+  /// Load a view from `buffer`, failing if validating it would exceed the
+  /// limits in `options`.
+  fn load_with<'buffer>(buffer: &'buffer [u8], options: &LoadOptions) -> Result<&'buffer Self> {
     let unchecked = Self::cast(buffer, 0)?;
 
-    let checked = Self::check(unchecked, buffer)?;
+    let mut validator = Validator::new(buffer, options);
+
+    validator.allocate(0..mem::size_of::<Self>())?;
 
-    let unchecked_pointer: *const MaybeUninit<Self> = unchecked;
-    let checked_pointer: *const Self = checked;
+    let checked = unchecked.check(&mut validator)?;
 
-    // TODO: This is required because the `check` implementation could return a
-    // reference to a valid value other than the one that was passed in. By doing
-    // this assertion, it is guaranteed that the value passed in is valid.
-    //
-    // If this doesn't optimize out, I should make the `check` function unsafe
-    // instead.
-    assert_eq!(checked_pointer as usize, unchecked_pointer as usize);
+    validator.finish()?;
 
     Ok(checked)
   }
@@ -55,7 +86,19 @@ pub trait View: Sized {
     Ok(unsafe { &*pointer })
   }
 
-  fn check<'value>(suspect: &'value MaybeUninit<Self>, buffer: &[u8]) -> Result<&'value Self>;
+  /// Check that `suspect` is a valid value of `Self`.
+  fn check(suspect: &MaybeUninit<Self>, validator: &mut Validator) -> Result<()>;
+
+  /// Check consecutive suspects, such as the elements of a slice or array.
+  /// Views which can be checked faster in bulk than one at a time override
+  /// this, and must return the same error as the default implementation.
+  fn check_slice(suspects: &[MaybeUninit<Self>], validator: &mut Validator) -> Result<()> {
+    for (index, suspect) in suspects.iter().enumerate() {
+      Self::check(suspect, validator).map_err(|error| error.at(Segment::Index(index)))?;
+    }
+
+    Ok(())
+  }
 }
 
 // TODO: reenable
@@ -80,10 +123,10 @@ mod tests {
       }
     }
 
-    impl View for Foo {
+    unsafe impl View for Foo {
       type Serializer<A: Allocator, C: Continuation<A>> = Foo;
 
-      fn check<'value>(_: &'value MaybeUninit<Self>, _: &[u8]) -> Result<&'value Self> {
+      fn check(_: &MaybeUninit<Self>, _: &mut Validator) -> Result<()> {
         panic!()
       }
     }
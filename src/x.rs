@@ -23,10 +23,16 @@ pub trait X: Sized {
     serializer: <Self::View as View>::Serializer<A, C>,
   ) -> C;
 
+  /// The number of bytes `self` will serialize to.
+  fn serialized_size(&self) -> Result<usize, LengthMismatch> {
+    Self::store(SizeAllocator::new()).serialize(self).done()
+  }
+
+  /// Serialize into `slice`, returning an error if the message does not fit.
   fn store_to_slice(
     slice: &mut [u8],
-  ) -> <Self::View as View>::Serializer<SliceAllocator, Done<SliceAllocator>> {
-    Self::store(SliceAllocator::new(slice))
+  ) -> <Self::View as View>::Serializer<FallibleSliceAllocator, Done<FallibleSliceAllocator>> {
+    Self::store(FallibleSliceAllocator::new(slice))
   }
 
   #[cfg(feature = "alloc")]
@@ -35,7 +41,7 @@ pub trait X: Sized {
   }
 
   #[cfg(feature = "alloc")]
-  fn serialize_to_vec(&self) -> Vec<u8> {
+  fn serialize_to_vec(&self) -> Result<Vec<u8>, LengthMismatch> {
     Self::store(VecAllocator::new()).serialize(self).done()
   }
 
@@ -4,7 +4,11 @@
 #![feature(maybe_uninit_ref)]
 #![allow(incomplete_features)]
 
-use x::{Serializer, View, X};
+extern crate alloc;
+
+use alloc::{vec, vec::Vec};
+
+use x::{BitVec, Error, LazyView, Segment, Serializer, View, X};
 
 #[derive(X)]
 struct Record {
@@ -37,7 +41,8 @@ fn construct() {
     .e(&-2i32)
     .f(&true)
     .g(&127u8)
-    .done();
+    .done()
+    .unwrap();
 
   assert_eq!(have, want);
 
@@ -52,7 +57,8 @@ fn construct() {
     .e(&-2i32)
     .f(&true)
     .g(&127u8)
-    .done();
+    .done()
+    .unwrap();
 
   assert_eq!(have, want);
 
@@ -67,7 +73,8 @@ fn construct() {
       f: true,
       g: 127u8,
     })
-    .done();
+    .done()
+    .unwrap();
 
   assert_eq!(have, want);
 
@@ -86,3 +93,122 @@ fn construct() {
   // assert_eq!(foo.g(), 127);
   // assert_eq!(foo.g, 127);
 }
+
+#[derive(X)]
+struct Flags {
+  a: BitVec,
+  b: Vec<u8>,
+}
+
+#[test]
+fn nested() {
+  let a = [true, false, true].iter().copied().collect::<BitVec>();
+
+  let have = Flags::store_to_vec().a(&a).b(&vec![7u8, 8]).done().unwrap();
+
+  #[rustfmt::skip]
+  let want: &[u8] = &[
+    // a offset
+    32, 0, 0, 0, 0, 0, 0, 0,
+    // a length
+    3, 0, 0, 0, 0, 0, 0, 0,
+    // b offset
+    17, 0, 0, 0, 0, 0, 0, 0,
+    // b length
+    2, 0, 0, 0, 0, 0, 0, 0,
+    // a contents
+    0b101,
+    // b contents
+    7, 8,
+  ];
+
+  assert_eq!(have, want);
+
+  let view = FlagsView::load(&have).unwrap();
+
+  assert_eq!(view.a.count_ones(), 2);
+  assert_eq!(view.b.as_slice(), &[7, 8]);
+}
+
+#[derive(X)]
+struct Items {
+  items: Vec<Item>,
+}
+
+#[derive(X)]
+struct Item {
+  id:   u8,
+  flag: bool,
+}
+
+#[test]
+fn error_path() {
+  let mut buffer = Items {
+    items: vec![
+      Item {
+        id:   0,
+        flag: true,
+      },
+      Item {
+        id:   1,
+        flag: false,
+      },
+    ],
+  }
+  .serialize_to_vec()
+  .unwrap();
+
+  // items[1].flag
+  buffer[19] = 2;
+
+  let error = ItemsView::load(&buffer).err().unwrap();
+
+  assert_eq!(error.path().collect::<Vec<Segment>>(), &[
+    Segment::Field("items"),
+    Segment::Index(1),
+    Segment::Field("flag"),
+  ]);
+
+  assert_eq!(error.kind(), &Error::Bool { value: 2 });
+}
+
+#[test]
+fn lazy() {
+  let mut buffer = Items {
+    items: vec![
+      Item {
+        id:   0,
+        flag: true,
+      },
+      Item {
+        id:   1,
+        flag: false,
+      },
+    ],
+  }
+  .serialize_to_vec()
+  .unwrap();
+
+  // items[1].flag
+  buffer[19] = 2;
+
+  let lazy = LazyView::<ItemsView>::load(&buffer).unwrap();
+
+  let items = lazy
+    .project(|view| unsafe { &raw const (*view).items })
+    .unwrap();
+
+  assert_eq!(items.len(), 2);
+
+  let item = items.get(0).unwrap().unwrap().validate().unwrap();
+  assert_eq!(item.id, 0);
+  assert!(item.flag);
+
+  let error = items.get(1).unwrap().err().unwrap();
+  assert_eq!(error.path().collect::<Vec<Segment>>(), &[
+    Segment::Index(1),
+    Segment::Field("flag")
+  ]);
+
+  assert!(lazy.validate().is_err());
+}
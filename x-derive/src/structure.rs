@@ -145,6 +145,8 @@ impl Tokens for Structure {
 
     let accessors = self.field_accessors();
 
+    let names = accessors.iter().map(|accessor| accessor.to_string());
+
     let x = &self.krate;
 
     let body = match &self.input.fields {
@@ -221,13 +223,13 @@ impl Tokens for Structure {
 
       // #view_getters
 
-      impl #x::View for #view {
+      unsafe impl #x::View for #view {
         type Serializer<A: #x::Allocator, C: #x::Continuation<A>> = #first_serializer<A, C>;
 
-        fn check<'value>(
-          suspect: &'value #x::core::mem::MaybeUninit<Self>,
-          buffer: &[u8],
-        ) -> #x::Result<&'value Self> {
+        fn check(
+          suspect: &#x::core::mem::MaybeUninit<Self>,
+          validator: &mut #x::Validator,
+        ) -> #x::Result<()> {
           let pointer: *const Self = suspect.as_ptr();
           #(
           {
@@ -235,11 +237,13 @@ impl Tokens for Structure {
             let field_pointer: *const FieldView = unsafe { &raw const (*pointer).#accessors };
             let maybe_uninit_pointer = field_pointer as *const #x::core::mem::MaybeUninit<FieldView>;
             let maybe_uninit_ref = unsafe { &*maybe_uninit_pointer } ;
-            FieldView::check(maybe_uninit_ref, buffer)?;
+            FieldView::check(maybe_uninit_ref, validator)
+              .map_err(|error| error.at(#x::Segment::Field(#names)))?;
           }
           )*
-          // All fields are valid, so the struct is valid.
-          Ok(unsafe { suspect.assume_init_ref() })
+          // All fields are valid, and views have alignment 1, so the struct has no
+          // padding, and is valid.
+          Ok(())
         }
       }
 
@@ -310,15 +314,15 @@ mod tests {
       #[repr(C)]
       struct FooView;
 
-      impl ::x::View for FooView {
+      unsafe impl ::x::View for FooView {
         type Serializer<A: ::x::Allocator, C: ::x::Continuation<A>> = FooSerializer<A, C>;
 
-        fn check<'value>(
-          suspect: &'value ::x::core::mem::MaybeUninit<Self>,
-          buffer: &[u8],
-        ) -> ::x::Result<&'value Self> {
+        fn check(
+          suspect: &::x::core::mem::MaybeUninit<Self>,
+          validator: &mut ::x::Validator,
+        ) -> ::x::Result<()> {
           let pointer: *const Self = suspect.as_ptr();
-          Ok(unsafe { suspect.assume_init_ref() })
+          Ok(())
         }
       }
 
@@ -359,29 +363,31 @@ mod tests {
         b: <String as ::x::X>::View,
       }
 
-      impl ::x::View for FooView {
+      unsafe impl ::x::View for FooView {
         type Serializer<A: ::x::Allocator, C: ::x::Continuation<A>> = FooSerializer<A, C>;
 
-        fn check<'value>(
-          suspect: &'value ::x::core::mem::MaybeUninit<Self>,
-          buffer: &[u8],
-        ) -> ::x::Result<&'value Self> {
+        fn check(
+          suspect: &::x::core::mem::MaybeUninit<Self>,
+          validator: &mut ::x::Validator,
+        ) -> ::x::Result<()> {
           let pointer: *const Self = suspect.as_ptr();
           {
             type FieldView = <u16 as ::x::X>::View;
             let field_pointer: *const FieldView = unsafe { &raw const (*pointer).a };
             let maybe_uninit_pointer = field_pointer as *const ::x::core::mem::MaybeUninit<FieldView>;
             let maybe_uninit_ref = unsafe { &*maybe_uninit_pointer } ;
-            FieldView::check(maybe_uninit_ref, buffer)?;
+            FieldView::check(maybe_uninit_ref, validator)
+              .map_err(|error| error.at(::x::Segment::Field("a")))?;
           }
           {
             type FieldView = <String as ::x::X>::View;
             let field_pointer: *const FieldView = unsafe { &raw const (*pointer).b };
             let maybe_uninit_pointer = field_pointer as *const ::x::core::mem::MaybeUninit<FieldView>;
             let maybe_uninit_ref = unsafe { &*maybe_uninit_pointer } ;
-            FieldView::check(maybe_uninit_ref, buffer)?;
+            FieldView::check(maybe_uninit_ref, validator)
+              .map_err(|error| error.at(::x::Segment::Field("b")))?;
           }
-          Ok(unsafe { suspect.assume_init_ref() })
+          Ok(())
         }
       }
 
@@ -452,29 +458,31 @@ mod tests {
       #[repr(C)]
       struct FooView(<u16 as ::x::X>::View, <String as ::x::X>::View,);
 
-      impl ::x::View for FooView {
+      unsafe impl ::x::View for FooView {
         type Serializer<A: ::x::Allocator, C: ::x::Continuation<A>> = FooSerializer<A, C>;
 
-        fn check<'value>(
-          suspect: &'value ::x::core::mem::MaybeUninit<Self>,
-          buffer: &[u8],
-        ) -> ::x::Result<&'value Self> {
+        fn check(
+          suspect: &::x::core::mem::MaybeUninit<Self>,
+          validator: &mut ::x::Validator,
+        ) -> ::x::Result<()> {
           let pointer: *const Self = suspect.as_ptr();
           {
             type FieldView = <u16 as ::x::X>::View;
             let field_pointer: *const FieldView = unsafe { &raw const (*pointer).0 };
             let maybe_uninit_pointer = field_pointer as *const ::x::core::mem::MaybeUninit<FieldView>;
             let maybe_uninit_ref = unsafe { &*maybe_uninit_pointer } ;
-            FieldView::check(maybe_uninit_ref, buffer)?;
+            FieldView::check(maybe_uninit_ref, validator)
+              .map_err(|error| error.at(::x::Segment::Field("0")))?;
           }
           {
             type FieldView = <String as ::x::X>::View;
             let field_pointer: *const FieldView = unsafe { &raw const (*pointer).1 };
             let maybe_uninit_pointer = field_pointer as *const ::x::core::mem::MaybeUninit<FieldView>;
             let maybe_uninit_ref = unsafe { &*maybe_uninit_pointer } ;
-            FieldView::check(maybe_uninit_ref, buffer)?;
+            FieldView::check(maybe_uninit_ref, validator)
+              .map_err(|error| error.at(::x::Segment::Field("1")))?;
           }
-          Ok(unsafe { suspect.assume_init_ref() })
+          Ok(())
         }
       }
 